@@ -1,24 +1,30 @@
 use termion::{
-    color::{Bg, Fg, Reset, Rgb},
+    color::Rgb,
     cursor::Goto,
+    style::{NoUnderline, Underline},
 };
 
-use super::{color::Color, vector::Vector};
+use crate::core::theme::Theme;
+
+use super::{color::ColorMode, glyph::GlyphMode, vector::Vector};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum CellType {
     Solid,
     Fruit,
     Snake,
+    Hazard,
+    Portal,
     Empty,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Cell {
     char: char,
     bg_color: Rgb,
     fg_color: Rgb,
     cell_type: CellType,
+    underlined: bool,
 }
 
 impl Cell {
@@ -28,6 +34,7 @@ impl Cell {
             bg_color,
             fg_color,
             cell_type,
+            underlined: false,
         };
     }
 
@@ -37,39 +44,120 @@ impl Cell {
             bg_color,
             fg_color,
             cell_type: CellType::Solid,
+            underlined: false,
         };
     }
 
+    /// Uses the background and text colors of the current theme.
     pub fn new_colorless(char: char) -> Cell {
+        let theme = Theme::current();
+
         return Cell {
             char,
-            bg_color: Color::Black.to_rgb(),
-            fg_color: Color::White.to_rgb(),
+            bg_color: theme.background(),
+            fg_color: theme.text(),
             cell_type: CellType::Solid,
+            underlined: false,
         };
     }
 
     pub fn new_empty() -> Cell {
+        let theme = Theme::current();
+
         return Cell {
             char: ' ',
-            bg_color: Color::Black.to_rgb(),
-            fg_color: Color::White.to_rgb(),
+            bg_color: theme.background(),
+            fg_color: theme.text(),
             cell_type: CellType::Empty,
+            underlined: false,
         };
     }
 
+    pub fn char(&self) -> char {
+        return self.char;
+    }
+
+    pub fn bg_color(&self) -> Rgb {
+        return self.bg_color;
+    }
+
+    pub fn fg_color(&self) -> Rgb {
+        return self.fg_color;
+    }
+
     pub fn cell_type(&self) -> CellType {
         return self.cell_type;
     }
 
+    pub fn set_underlined(&mut self, underlined: bool) {
+        self.underlined = underlined;
+    }
+
+    /// Returns a copy with both colors darkened by the factor, between 0 and 1.
+    pub fn dimmed(&self, factor: f32) -> Cell {
+        return Cell {
+            char: self.char,
+            bg_color: scaled_color(self.bg_color, factor),
+            fg_color: scaled_color(self.fg_color, factor),
+            cell_type: self.cell_type,
+            underlined: self.underlined,
+        };
+    }
+
+    /// Blends the colors towards the other cell, switching characters halfway through.
+    pub fn interpolated(&self, other: &Cell, progress: f32) -> Cell {
+        let source = if progress < 0.5 { self } else { other };
+
+        return Cell {
+            char: source.char,
+            bg_color: interpolated_color(self.bg_color, other.bg_color, progress),
+            fg_color: interpolated_color(self.fg_color, other.fg_color, progress),
+            cell_type: source.cell_type,
+            underlined: source.underlined,
+        };
+    }
+
     pub fn to_string(&self, position: &Vector<u16>) -> String {
+        let (color_start, color_end) =
+            ColorMode::current().escape_codes(self.bg_color, self.fg_color);
+        let char = GlyphMode::current().displayed(self.char);
+
+        if self.underlined {
+            return format!(
+                "{}{}{}{}{}{}",
+                Goto(position.x() + 1, position.y() + 1),
+                color_start,
+                Underline,
+                char,
+                NoUnderline,
+                color_end,
+            );
+        }
+
         return format!(
-            "{}{}{}{}{}",
+            "{}{}{}{}",
             Goto(position.x() + 1, position.y() + 1),
-            Bg(self.bg_color),
-            Fg(self.fg_color),
-            self.char,
-            Fg(Reset),
+            color_start,
+            char,
+            color_end,
         );
     }
 }
+
+fn scaled_color(color: Rgb, factor: f32) -> Rgb {
+    return Rgb(
+        (color.0 as f32 * factor) as u8,
+        (color.1 as f32 * factor) as u8,
+        (color.2 as f32 * factor) as u8,
+    );
+}
+
+pub fn interpolated_color(from: Rgb, to: Rgb, progress: f32) -> Rgb {
+    let channel = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * progress) as u8;
+
+    return Rgb(
+        channel(from.0, to.0),
+        channel(from.1, to.1),
+        channel(from.2, to.2),
+    );
+}
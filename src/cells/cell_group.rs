@@ -21,6 +21,13 @@ impl CellGroup {
         self.cells.insert(position, cell);
     }
 
+    /// Erases every cell on the next render.
+    pub fn clear(&mut self) {
+        for cell in self.cells.values_mut() {
+            *cell = Cell::new_empty();
+        }
+    }
+
     pub fn render(&mut self, cell_matrix: &mut CellMatrix) {
         for (vector, cell) in &self.cells {
             cell_matrix.set_cell(vector, cell.clone());
@@ -29,6 +36,11 @@ impl CellGroup {
         self.strip_empty();
     }
 
+    /// Drops the erased cells without writing them, for renders that start from a clean matrix.
+    pub fn discard_erased(&mut self) {
+        self.strip_empty();
+    }
+
     fn strip_empty(&mut self) {
         self.cells
             .retain(|_, cell| cell.cell_type() != CellType::Empty);
@@ -2,16 +2,27 @@ use crate::{cells::cell::Cell, core::terminal::Terminal};
 
 use super::vector::Vector;
 
+/// How the cells are drawn on the terminal.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Presentation {
+    /// Each cell is a terminal character.
+    Characters,
+    /// Each terminal character shows two cells on top of each other with a half block, so the
+    /// cells are square and there are twice as many rows.
+    HalfBlocks,
+}
+
 pub struct CellMatrix {
     matrix: Vec<Cell>,
     print_buffer: Vec<String>,
     width: u16,
     height: u16,
+    presentation: Presentation,
 }
 
 impl CellMatrix {
     pub fn new(width: u16, height: u16) -> CellMatrix {
-        let mut matrix = Vec::with_capacity((width * height) as usize);
+        let mut matrix = Vec::with_capacity(width as usize * height as usize);
         let mut print_buffer = Vec::new();
 
         for y in 0..height {
@@ -28,9 +39,21 @@ impl CellMatrix {
             print_buffer,
             width,
             height,
+            presentation: Presentation::Characters,
         };
     }
 
+    /// Matrix with two rows of cells for each of the terminal rows.
+    pub fn new_half_blocks(width: u16, terminal_height: u16) -> CellMatrix {
+        let mut cell_matrix = CellMatrix::new(width, terminal_height);
+
+        cell_matrix.height = terminal_height * 2;
+        cell_matrix.matrix = vec![Cell::new_empty(); width as usize * terminal_height as usize * 2];
+        cell_matrix.presentation = Presentation::HalfBlocks;
+
+        return cell_matrix;
+    }
+
     pub fn width(&self) -> u16 {
         return self.width;
     }
@@ -39,6 +62,28 @@ impl CellMatrix {
         return self.height;
     }
 
+    pub fn presentation(&self) -> Presentation {
+        return self.presentation;
+    }
+
+    /// Number of terminal rows the cells take.
+    pub fn terminal_height(&self) -> u16 {
+        return match self.presentation {
+            Presentation::Characters => self.height,
+            Presentation::HalfBlocks => self.height / 2,
+        };
+    }
+
+    /// Returns the cell drawn at a position of the terminal, for example under the mouse.
+    pub fn cell_position(&self, terminal_position: &Vector<u16>) -> Vector<u16> {
+        return match self.presentation {
+            Presentation::Characters => terminal_position.clone(),
+            Presentation::HalfBlocks => {
+                Vector::<u16>::new(terminal_position.x(), terminal_position.y() * 2)
+            }
+        };
+    }
+
     pub fn get_cell(&self, position: &Vector<u16>) -> Option<&Cell> {
         if !self.is_in_bounds(position) {
             return None;
@@ -54,8 +99,22 @@ impl CellMatrix {
 
         let index = self.get_index(position);
 
-        self.print_buffer.push(cell.to_string(position));
         self.matrix[index] = cell;
+
+        match self.presentation {
+            Presentation::Characters => {
+                self.print_buffer
+                    .push(self.matrix[index].to_string(position));
+            }
+            Presentation::HalfBlocks => {
+                let terminal_position = Vector::<u16>::new(position.x(), position.y() / 2);
+
+                self.print_buffer.push(
+                    self.presented_cell(&terminal_position)
+                        .to_string(&terminal_position),
+                );
+            }
+        }
     }
 
     pub fn write(&mut self, terminal: &mut Terminal) {
@@ -66,6 +125,92 @@ impl CellMatrix {
         self.print_buffer.clear();
     }
 
+    /// Queues every cell to be written again, for when something drawn over them is gone.
+    pub fn redraw(&mut self) {
+        let presented = self.presented();
+
+        for y in 0..presented.height {
+            for x in 0..presented.width {
+                let position = Vector::<u16>::new(x, y);
+
+                self.print_buffer
+                    .push(presented.matrix[presented.get_index(&position)].to_string(&position));
+            }
+        }
+    }
+
+    /// Returns a copy of the cells without anything pending to be written.
+    pub fn snapshot(&self) -> CellMatrix {
+        return CellMatrix {
+            matrix: self.matrix.clone(),
+            print_buffer: Vec::new(),
+            width: self.width,
+            height: self.height,
+            presentation: self.presentation,
+        };
+    }
+
+    /// Returns a copy of the cells as they are drawn on the terminal, one for each character.
+    pub fn presented(&self) -> CellMatrix {
+        if self.presentation == Presentation::Characters {
+            return self.snapshot();
+        }
+
+        let terminal_height = self.terminal_height();
+        let mut matrix = Vec::with_capacity(self.width as usize * terminal_height as usize);
+
+        for y in 0..terminal_height {
+            for x in 0..self.width {
+                matrix.push(self.presented_cell(&Vector::<u16>::new(x, y)));
+            }
+        }
+
+        return CellMatrix {
+            matrix,
+            print_buffer: Vec::new(),
+            width: self.width,
+            height: terminal_height,
+            presentation: Presentation::Characters,
+        };
+    }
+
+    /// Returns a darkened snapshot of the matrix, used as a backdrop for overlays.
+    pub fn dimmed(&self, factor: f32) -> CellMatrix {
+        return CellMatrix {
+            matrix: self.matrix.iter().map(|cell| cell.dimmed(factor)).collect(),
+            print_buffer: Vec::new(),
+            width: self.width,
+            height: self.height,
+            presentation: self.presentation,
+        };
+    }
+
+    /// Copies every cell from a matrix of the same size.
+    pub fn copy_from(&mut self, other: &CellMatrix) {
+        for y in 0..self.height.min(other.height) {
+            for x in 0..self.width.min(other.width) {
+                let position = Vector::<u16>::new(x, y);
+
+                self.set_cell(&position, other.matrix[other.get_index(&position)].clone());
+            }
+        }
+    }
+
+    /// Copies only the cells that differ from a matrix of the same size, for matrices that are
+    /// already on screen.
+    pub fn update_from(&mut self, other: &CellMatrix) {
+        for y in 0..self.height.min(other.height) {
+            for x in 0..self.width.min(other.width) {
+                let position = Vector::<u16>::new(x, y);
+                let cell = &other.matrix[other.get_index(&position)];
+
+                if self.matrix[self.get_index(&position)] != *cell {
+                    self.set_cell(&position, cell.clone());
+                }
+            }
+        }
+    }
+
     pub fn clear(&mut self) {
         for y in 0..self.height {
             for x in 0..self.width {
@@ -74,8 +219,52 @@ impl CellMatrix {
         }
     }
 
+    /// Combines the two cells shown by a terminal character with half blocks. Text is shown as
+    /// it is, hiding the other cell, and anything else becomes a block of its color.
+    fn presented_cell(&self, terminal_position: &Vector<u16>) -> Cell {
+        let top = &self.matrix[self.get_index(&Vector::<u16>::new(
+            terminal_position.x(),
+            terminal_position.y() * 2,
+        ))];
+        let bottom = &self.matrix[self.get_index(&Vector::<u16>::new(
+            terminal_position.x(),
+            terminal_position.y() * 2 + 1,
+        ))];
+
+        if top.char().is_ascii_graphic() {
+            return top.clone();
+        }
+
+        if bottom.char().is_ascii_graphic() {
+            return bottom.clone();
+        }
+
+        let block_color = |cell: &Cell| {
+            if cell.char() == ' ' {
+                return cell.bg_color();
+            }
+
+            return cell.fg_color();
+        };
+
+        let top_color = block_color(top);
+        let bottom_color = block_color(bottom);
+
+        if top_color == bottom_color {
+            return Cell::new_typeless(' ', top_color, top_color);
+        }
+
+        // The drawn half is the one that isn't empty, so nothing is lost with fonts that leave a
+        // gap around block characters
+        if top.char() == ' ' {
+            return Cell::new_typeless('▄', top_color, bottom_color);
+        }
+
+        return Cell::new_typeless('▀', bottom_color, top_color);
+    }
+
     fn get_index(&self, position: &Vector<u16>) -> usize {
-        return (position.y() * self.width + position.x()) as usize;
+        return position.y() as usize * self.width as usize + position.x() as usize;
     }
 
     fn is_in_bounds(&self, position: &Vector<u16>) -> bool {
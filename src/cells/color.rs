@@ -1,4 +1,25 @@
-use termion::color::Rgb;
+use std::{env, sync::OnceLock};
+
+use termion::{
+    color::{AnsiValue, Bg, Fg, Reset, Rgb},
+    style::{Invert, NoInvert},
+};
+
+static COLOR_MODE: OnceLock<ColorMode> = OnceLock::new();
+
+/// Levels of each channel in the 6x6x6 color cube of the 256 color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+const GRAYSCALE_START: u8 = 232;
+
+/// How many colors the terminal can show. Colors are always defined as RGB and reduced to the
+/// closest one the terminal supports when written.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    Monochrome,
+}
 
 #[allow(dead_code)]
 pub enum Color {
@@ -21,6 +42,29 @@ pub enum Color {
 }
 
 impl Color {
+    /// Parses a snake case color name, like `light_green`.
+    pub fn from_name(name: &str) -> Option<Color> {
+        return match name {
+            "black" => Some(Color::Black),
+            "red" => Some(Color::Red),
+            "green" => Some(Color::Green),
+            "yellow" => Some(Color::Yellow),
+            "blue" => Some(Color::Blue),
+            "magenta" => Some(Color::Magenta),
+            "cyan" => Some(Color::Cyan),
+            "white" => Some(Color::White),
+            "light_black" => Some(Color::LightBlack),
+            "light_red" => Some(Color::LightRed),
+            "light_green" => Some(Color::LightGreen),
+            "light_yellow" => Some(Color::LightYellow),
+            "light_blue" => Some(Color::LightBlue),
+            "light_magenta" => Some(Color::LightMagenta),
+            "light_cyan" => Some(Color::LightCyan),
+            "light_white" => Some(Color::LightWhite),
+            _ => None,
+        };
+    }
+
     pub fn to_rgb(&self) -> Rgb {
         return match self {
             Color::Black => Rgb(0, 0, 0),
@@ -42,3 +86,211 @@ impl Color {
         };
     }
 }
+
+impl ColorMode {
+    pub fn from_name(name: &str) -> Option<ColorMode> {
+        return match name {
+            "truecolor" | "24bit" => Some(ColorMode::TrueColor),
+            "256" => Some(ColorMode::Ansi256),
+            "16" => Some(ColorMode::Ansi16),
+            "none" | "monochrome" => Some(ColorMode::Monochrome),
+            _ => None,
+        };
+    }
+
+    /// Guesses the color support from the `NO_COLOR`, `COLORTERM` and `TERM` environment
+    /// variables.
+    pub fn detect() -> ColorMode {
+        let variable = |name: &str| env::var(name).unwrap_or_default();
+        let color_term = variable("COLORTERM");
+        let term = variable("TERM");
+
+        if !variable("NO_COLOR").is_empty() || term == "dumb" {
+            return ColorMode::Monochrome;
+        }
+
+        if color_term == "truecolor" || color_term == "24bit" || term.ends_with("-direct") {
+            return ColorMode::TrueColor;
+        }
+
+        if term.contains("256color")
+            || ["xterm", "screen", "tmux"]
+                .iter()
+                .any(|prefix| term.starts_with(prefix))
+        {
+            return ColorMode::Ansi256;
+        }
+
+        return ColorMode::Ansi16;
+    }
+
+    /// The mode set at startup, or the detected one if none was set.
+    pub fn current() -> ColorMode {
+        return *COLOR_MODE.get_or_init(ColorMode::detect);
+    }
+
+    /// Only the first call has an effect.
+    pub fn set_current(color_mode: ColorMode) {
+        let _ = COLOR_MODE.set(color_mode);
+    }
+
+    /// Returns the escape codes that go before and after a character with the colors.
+    pub fn escape_codes(&self, bg_color: Rgb, fg_color: Rgb) -> (String, String) {
+        return match self {
+            ColorMode::TrueColor => (
+                format!("{}{}", Bg(bg_color), Fg(fg_color)),
+                Fg(Reset).to_string(),
+            ),
+            ColorMode::Ansi256 => (
+                format!(
+                    "{}{}",
+                    Bg(AnsiValue(ansi_256_index(bg_color))),
+                    Fg(AnsiValue(ansi_256_index(fg_color)))
+                ),
+                Fg(Reset).to_string(),
+            ),
+            ColorMode::Ansi16 => {
+                let bg_index = ansi_16_index(bg_color);
+                let fg_index = ansi_16_index(fg_color);
+
+                // termion writes even the basic colors as 256 color codes, which some consoles
+                // don't understand
+                (
+                    format!(
+                        "\x1b[{}m\x1b[{}m",
+                        if bg_index < 8 {
+                            40 + bg_index
+                        } else {
+                            92 + bg_index
+                        },
+                        if fg_index < 8 {
+                            30 + fg_index
+                        } else {
+                            82 + fg_index
+                        },
+                    ),
+                    format!("{}{}", Fg(Reset), Bg(Reset)),
+                )
+            }
+            // Light backgrounds, like on a selected button, are shown in reverse video so they
+            // still stand out
+            ColorMode::Monochrome if brightness(bg_color) > brightness(fg_color) => {
+                (Invert.to_string(), NoInvert.to_string())
+            }
+            ColorMode::Monochrome => (String::new(), String::new()),
+        };
+    }
+}
+
+const ANSI_16_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+    Color::LightBlack,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::LightWhite,
+];
+
+fn squared_distance(a: Rgb, b: Rgb) -> u32 {
+    let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+
+    return channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2);
+}
+
+fn brightness(color: Rgb) -> u32 {
+    return color.0 as u32 + color.1 as u32 + color.2 as u32;
+}
+
+/// Closest color of the cube or the grayscale ramp of the 256 color palette.
+fn ansi_256_index(color: Rgb) -> u8 {
+    let level = |channel: u8| {
+        return (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - channel as i32).abs())
+            .unwrap_or(0);
+    };
+    let (r, g, b) = (level(color.0), level(color.1), level(color.2));
+    let cube_color = Rgb(CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+
+    // The ramp goes from 8 to 238 in steps of 10
+    let average = (color.0 as u32 + color.1 as u32 + color.2 as u32) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + gray_step * 10;
+
+    if squared_distance(Rgb(gray, gray, gray), color) < squared_distance(cube_color, color) {
+        return GRAYSCALE_START + gray_step;
+    }
+
+    return 16 + 36 * r as u8 + 6 * g as u8 + b as u8;
+}
+
+fn ansi_16_index(color: Rgb) -> u8 {
+    return (0..ANSI_16_COLORS.len())
+        .min_by_key(|&i| squared_distance(ANSI_16_COLORS[i].to_rgb(), color))
+        .unwrap_or(0) as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use termion::{color::Rgb, style::Invert};
+
+    use super::{ansi_16_index, ansi_256_index, ColorMode};
+
+    #[test]
+    fn ansi_256_uses_the_color_cube() {
+        assert_eq!(ansi_256_index(Rgb(0, 0, 0)), 16);
+        assert_eq!(ansi_256_index(Rgb(255, 255, 255)), 231);
+        assert_eq!(ansi_256_index(Rgb(255, 0, 0)), 196);
+        assert_eq!(ansi_256_index(Rgb(95, 135, 175)), 67);
+        // Channels go to the closest level, not the one below
+        assert_eq!(ansi_256_index(Rgb(250, 10, 100)), 16 + 36 * 5 + 1);
+    }
+
+    #[test]
+    fn ansi_256_uses_the_grayscale_ramp_for_closer_grays() {
+        assert_eq!(ansi_256_index(Rgb(128, 128, 128)), 244);
+        assert_eq!(ansi_256_index(Rgb(8, 8, 8)), 232);
+        assert_eq!(ansi_256_index(Rgb(238, 238, 238)), 255);
+    }
+
+    #[test]
+    fn ansi_16_picks_the_closest_basic_color() {
+        assert_eq!(ansi_16_index(Rgb(0, 0, 0)), 0);
+        assert_eq!(ansi_16_index(Rgb(250, 5, 5)), 1);
+        assert_eq!(ansi_16_index(Rgb(120, 130, 125)), 8);
+        assert_eq!(ansi_16_index(Rgb(255, 120, 130)), 9);
+        // Ties go to the normal color before its light version
+        assert_eq!(ansi_16_index(Rgb(255, 255, 255)), 7);
+    }
+
+    #[test]
+    fn ansi_16_writes_basic_escape_codes() {
+        let (start, _) = ColorMode::Ansi16.escape_codes(Rgb(0, 0, 0), Rgb(255, 0, 0));
+
+        assert_eq!(start, "\x1b[40m\x1b[31m");
+
+        let (start, _) = ColorMode::Ansi16.escape_codes(Rgb(128, 128, 255), Rgb(255, 128, 128));
+
+        assert_eq!(start, "\x1b[104m\x1b[91m");
+    }
+
+    #[test]
+    fn monochrome_inverts_light_backgrounds() {
+        let (start, _) = ColorMode::Monochrome.escape_codes(Rgb(255, 255, 255), Rgb(0, 0, 0));
+
+        assert_eq!(start, Invert.to_string());
+        assert!(ColorMode::Monochrome
+            .escape_codes(Rgb(0, 0, 0), Rgb(255, 255, 255))
+            .0
+            .is_empty());
+    }
+}
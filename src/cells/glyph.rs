@@ -0,0 +1,186 @@
+use std::{cell::Cell, env, sync::OnceLock};
+
+use crate::core::settings::Settings;
+
+static GLYPH_MODE: OnceLock<GlyphMode> = OnceLock::new();
+
+thread_local! {
+    static SHAPE_CODING: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Which characters the terminal is expected to show.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GlyphMode {
+    Unicode,
+    /// For serial consoles and fonts without box drawing or block characters.
+    Ascii,
+}
+
+/// Characters with a meaning in the game, which have their own ASCII replacement instead of the
+/// generic fallback.
+#[derive(Clone, Copy)]
+pub enum Glyph {
+    WallTopLeft,
+    WallTopRight,
+    WallBottomLeft,
+    WallBottomRight,
+    WallHorizontal,
+    WallVertical,
+    SnakeHead,
+    /// Shade of a body segment, from 0 (solid) to 3 (lightest).
+    SnakeBody(usize),
+    Fruit,
+    Patroller,
+    /// Orientation of a rotating bar, in steps of 45 degrees.
+    Bar(usize),
+    Gate,
+    Portal,
+    Collision,
+}
+
+impl GlyphMode {
+    pub fn from_name(name: &str) -> Option<GlyphMode> {
+        return match name {
+            "unicode" => Some(GlyphMode::Unicode),
+            "ascii" => Some(GlyphMode::Ascii),
+            _ => None,
+        };
+    }
+
+    /// Uses Unicode if the locale from `LC_ALL`, `LC_CTYPE` or `LANG` is UTF-8.
+    pub fn detect() -> GlyphMode {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        if locale.contains("utf-8") || locale.contains("utf8") {
+            return GlyphMode::Unicode;
+        }
+
+        return GlyphMode::Ascii;
+    }
+
+    /// The mode set at startup, or the detected one if none was set.
+    pub fn current() -> GlyphMode {
+        return *GLYPH_MODE.get_or_init(GlyphMode::detect);
+    }
+
+    /// Only the first call has an effect.
+    pub fn set_current(glyph_mode: GlyphMode) {
+        let _ = GLYPH_MODE.set(glyph_mode);
+    }
+
+    /// Returns the character that is written for the one in a cell.
+    pub fn displayed(&self, char: char) -> char {
+        return match self {
+            GlyphMode::Unicode => char,
+            GlyphMode::Ascii => ascii_fallback(char),
+        };
+    }
+}
+
+impl Glyph {
+    /// Whether the entities use shapes that tell them apart without their colors, taken from the
+    /// settings until it is set.
+    pub fn shape_coding() -> bool {
+        return SHAPE_CODING.with(|shape_coding| match shape_coding.get() {
+            Some(shape_coding) => shape_coding,
+            None => {
+                let loaded = Settings::load().shape_coding();
+
+                shape_coding.set(Some(loaded));
+
+                loaded
+            }
+        });
+    }
+
+    pub fn set_shape_coding(shape_coding: bool) {
+        SHAPE_CODING.with(|current| current.set(Some(shape_coding)));
+    }
+
+    pub fn char(&self) -> char {
+        const SNAKE_BODY: [char; 4] = ['█', '▓', '▒', '░'];
+        const BARS: [char; 4] = ['─', '╲', '│', '╱'];
+        const ASCII_BARS: [char; 4] = ['-', '\\', '|', '/'];
+
+        let (unicode, ascii) = match self {
+            Glyph::WallTopLeft => ('┏', '+'),
+            Glyph::WallTopRight => ('┓', '+'),
+            Glyph::WallBottomLeft => ('┗', '+'),
+            Glyph::WallBottomRight => ('┛', '+'),
+            Glyph::WallHorizontal => ('━', '-'),
+            Glyph::WallVertical => ('┃', '|'),
+            Glyph::SnakeHead => ('█', '@'),
+            Glyph::SnakeBody(shade) => (SNAKE_BODY[shade % SNAKE_BODY.len()], '#'),
+            Glyph::Fruit => ('■', 'o'),
+            Glyph::Patroller => ('◆', '*'),
+            Glyph::Bar(phase) => (BARS[phase % BARS.len()], ASCII_BARS[phase % BARS.len()]),
+            Glyph::Gate => ('▒', '%'),
+            Glyph::Portal => ('◎', 'O'),
+            Glyph::Collision => ('✖', 'X'),
+        };
+
+        return match GlyphMode::current() {
+            GlyphMode::Unicode if Glyph::shape_coding() => {
+                self.shape_coded_char().unwrap_or(unicode)
+            }
+            GlyphMode::Unicode => unicode,
+            GlyphMode::Ascii => ascii,
+        };
+    }
+
+    /// Replacements for the glyphs that only differ from another one by their color or shade. The
+    /// ASCII set doesn't need any, each of its characters is already distinct.
+    fn shape_coded_char(&self) -> Option<char> {
+        return match self {
+            Glyph::SnakeHead => Some('▣'),
+            Glyph::Fruit => Some('♥'),
+            Glyph::Gate => Some('▦'),
+            _ => None,
+        };
+    }
+}
+
+/// Closest ASCII character for the box drawing, block, braille and symbol characters used in the
+/// interface.
+fn ascii_fallback(char: char) -> char {
+    if char.is_ascii() {
+        return char;
+    }
+
+    // Braille art gets denser characters for cells with more dots
+    if ('\u{2800}'..='\u{28ff}').contains(&char) {
+        return match (char as u32 - 0x2800).count_ones() {
+            0 => ' ',
+            1..=2 => '.',
+            3..=5 => ':',
+            _ => '#',
+        };
+    }
+
+    return match char {
+        '─' | '━' | '═' | '┄' | '┅' | '╌' | '╍' | '╴' | '╶' => '-',
+        '│' | '┃' | '║' | '┆' | '┇' | '╎' | '╏' | '╵' | '╷' => '|',
+        '╱' => '/',
+        '╲' => '\\',
+        '\u{2500}'..='\u{257f}' => '+',
+        '█' | '▓' | '■' | '▦' => '#',
+        '▣' => '@',
+        '♥' => 'o',
+        '▒' => '%',
+        '░' => '.',
+        '◀' => '<',
+        '▶' => '>',
+        '▲' => '^',
+        '▼' => 'v',
+        '●' => 'o',
+        '◆' => '*',
+        '◎' => 'O',
+        '✖' => 'X',
+        _ => '?',
+    };
+}
@@ -2,4 +2,5 @@ pub mod cell;
 pub mod cell_group;
 pub mod cell_matrix;
 pub mod color;
+pub mod glyph;
 pub mod vector;
\ No newline at end of file
@@ -1,6 +1,6 @@
 use num_traits::PrimInt;
 
-#[derive(Eq, Hash, PartialEq, Clone)]
+#[derive(Eq, Hash, PartialEq, Clone, Debug)]
 pub struct Vector<T>
 where
     T: PrimInt,
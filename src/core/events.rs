@@ -1,11 +1,32 @@
+use crate::gameplay::{death_cause::DeathCause, game_mode::GameMode};
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Event {
-    Start,
+    Start {
+        mode: GameMode,
+    },
     Pause,
     Resume,
+    Continue,
     Restart,
-    End,
+    End {
+        score: u32,
+        length: u32,
+        cause: DeathCause,
+    },
+    Abandon,
+    SaveAndQuit,
+    /// The game couldn't be saved, so it stays paused.
+    SaveFailed,
     GoToMenu,
+    ShowStatistics,
+    ShowSettings,
+    /// A widget's value was edited. Handled by the scene that owns the widget.
+    ValueChanged,
+    /// Closes the open dialog without doing anything.
+    Dismiss,
+    /// A different theme was picked. The scenes are built again with its colors.
+    ThemeChanged,
     Exit,
     None,
 }
@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+
+use termion::event::{parse_event, Event as TermionEvent, Key};
+
+use crate::cells::vector::Vector;
+
+#[derive(Clone)]
+pub enum MouseInput {
+    /// The pointer moved over the cell, with or without a button held.
+    Move(Vector<u16>),
+    Click(Vector<u16>),
+    ScrollUp,
+    ScrollDown,
+}
+
+#[derive(Clone)]
+pub enum Input {
+    Key(Key),
+    Mouse(MouseInput),
+}
+
+/// Parses every input in the bytes read from the terminal and returns how many bytes were used.
+/// termion can't parse mouse motion without a pressed button, so SGR mouse reports are parsed here
+/// and everything else is left to termion.
+///
+/// A sequence cut off at the end is left unparsed, as the next read brings the rest of it. Once a
+/// read brings nothing, `finished` is true and the cut off sequence is parsed as it is.
+pub fn parsed_inputs(bytes: &[u8], finished: bool, inputs: &mut VecDeque<Input>) -> usize {
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"\x1b[<") {
+            let Some(length) = bytes[i..]
+                .iter()
+                .position(|byte| *byte == b'M' || *byte == b'm')
+            else {
+                return if finished { bytes.len() } else { i };
+            };
+
+            if let Some(mouse_input) =
+                parsed_mouse_report(&bytes[i + 3..i + length], bytes[i + length])
+            {
+                inputs.push_back(Input::Mouse(mouse_input));
+            }
+
+            i += length + 1;
+
+            continue;
+        }
+
+        // An escape byte with nothing after it is the escape key, not the start of a sequence
+        if bytes[i] == b'\x1b' && i + 1 == bytes.len() {
+            if !finished {
+                return i;
+            }
+
+            inputs.push_back(Input::Key(Key::Esc));
+
+            return bytes.len();
+        }
+
+        let mut rest = bytes[i + 1..].iter();
+        let event = parse_event(bytes[i], &mut rest.by_ref().map(|byte| Ok(*byte)));
+
+        match event {
+            Ok(TermionEvent::Key(key)) => inputs.push_back(Input::Key(key)),
+            Err(_) if rest.as_slice().is_empty() && !finished => return i,
+            _ => (),
+        }
+
+        i = bytes.len() - rest.as_slice().len();
+    }
+
+    return i;
+}
+
+/// Parses the `button;x;y` part of a report. Coordinates start at one.
+fn parsed_mouse_report(report: &[u8], terminator: u8) -> Option<MouseInput> {
+    let report = std::str::from_utf8(report).ok()?;
+    let mut numbers = report.split(';').map(|number| number.parse::<u16>().ok());
+    let button = numbers.next()??;
+    let position = Vector::<u16>::new(
+        numbers.next()??.saturating_sub(1),
+        numbers.next()??.saturating_sub(1),
+    );
+
+    if button & 64 != 0 {
+        return Some(if button & 1 == 0 {
+            MouseInput::ScrollUp
+        } else {
+            MouseInput::ScrollDown
+        });
+    }
+
+    if button & 32 != 0 {
+        return Some(MouseInput::Move(position));
+    }
+
+    if terminator == b'M' && button & 3 == 0 {
+        return Some(MouseInput::Click(position));
+    }
+
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use termion::event::Key;
+
+    use super::{parsed_inputs, Input, MouseInput};
+    use crate::cells::vector::Vector;
+
+    fn keys(inputs: &VecDeque<Input>) -> Vec<Key> {
+        return inputs
+            .iter()
+            .filter_map(|input| match input {
+                Input::Key(key) => Some(*key),
+                Input::Mouse(_) => None,
+            })
+            .collect();
+    }
+
+    #[test]
+    fn mouse_report_split_across_reads() {
+        let mut inputs = VecDeque::new();
+        let first = b"a\x1b[<35;1";
+
+        let parsed = parsed_inputs(first, false, &mut inputs);
+
+        assert_eq!(parsed, 1);
+        assert_eq!(keys(&inputs), vec![Key::Char('a')]);
+
+        let mut bytes = first[parsed..].to_vec();
+
+        bytes.extend_from_slice(b"2;7Mb");
+        inputs.clear();
+
+        assert_eq!(parsed_inputs(&bytes, false, &mut inputs), bytes.len());
+        assert!(matches!(
+            inputs.front(),
+            Some(Input::Mouse(MouseInput::Move(position))) if *position == Vector::<u16>::new(11, 6)
+        ));
+        assert_eq!(keys(&inputs), vec![Key::Char('b')]);
+    }
+
+    #[test]
+    fn escape_waits_for_the_next_read() {
+        let mut inputs = VecDeque::new();
+
+        assert_eq!(parsed_inputs(b"\x1b", false, &mut inputs), 0);
+        assert!(inputs.is_empty());
+
+        // The rest of an arrow key arrives
+        assert_eq!(parsed_inputs(b"\x1b[A", false, &mut inputs), 3);
+        assert_eq!(keys(&inputs), vec![Key::Up]);
+
+        inputs.clear();
+
+        // Nothing else arrives, so it was the escape key
+        assert_eq!(parsed_inputs(b"\x1b", true, &mut inputs), 1);
+        assert_eq!(keys(&inputs), vec![Key::Esc]);
+    }
+
+    #[test]
+    fn cut_off_sequences_wait_for_the_next_read() {
+        let mut inputs = VecDeque::new();
+
+        assert_eq!(parsed_inputs(b"x\x1b[", false, &mut inputs), 1);
+        assert_eq!(parsed_inputs(b"\x1b[<0;1", true, &mut inputs), 6);
+        assert_eq!(keys(&inputs), vec![Key::Char('x')]);
+    }
+}
@@ -1,4 +1,11 @@
 pub mod chronometer;
 pub mod events;
+pub mod input;
+pub mod random;
 pub mod scene_manager;
+pub mod scene_registry;
+pub mod settings;
+pub mod storage;
 pub mod terminal;
+pub mod theme;
+pub mod transition;
@@ -0,0 +1,80 @@
+use std::ops::Range;
+
+use rand::{rng, Rng};
+
+/// Small xorshift generator whose whole state is a single number, so it can be saved and restored.
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new() -> Random {
+        return Random::from_state(rng().random());
+    }
+
+    pub fn from_state(state: u64) -> Random {
+        // Zero is the only state xorshift can't leave
+        return Random {
+            state: if state == 0 { 1 } else { state },
+        };
+    }
+
+    pub fn state(&self) -> u64 {
+        return self.state;
+    }
+
+    pub fn random_range(&mut self, range: Range<u16>) -> u16 {
+        let length = (range.end - range.start) as u64;
+
+        return range.start + (self.next() % length) as u16;
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+
+        return self.state.wrapping_mul(0x2545_F491_4F6C_DD1D);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restored_state_continues_the_same_sequence() {
+        let mut random = Random::from_state(12345);
+
+        for _ in 0..10 {
+            random.random_range(0..100);
+        }
+
+        let mut restored = Random::from_state(random.state());
+
+        for _ in 0..100 {
+            assert_eq!(random.random_range(0..1000), restored.random_range(0..1000));
+        }
+    }
+
+    #[test]
+    fn zero_state_is_replaced() {
+        let mut random = Random::from_state(0);
+
+        assert_ne!(random.state(), 0);
+
+        random.random_range(0..10);
+        assert_ne!(random.state(), 0);
+    }
+
+    #[test]
+    fn values_stay_in_the_range() {
+        let mut random = Random::from_state(7);
+
+        for _ in 0..1000 {
+            assert!((5..9).contains(&random.random_range(5..9)));
+        }
+
+        assert_eq!(random.random_range(3..4), 3);
+    }
+}
@@ -1,106 +1,189 @@
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, HashMap};
 
-use crate::scenes::{gameplay_scene::GameplayScene, scene::Scene};
+use crate::{
+    cells::cell_matrix::CellMatrix,
+    scenes::scene::{Scene, SceneId},
+    TRANSITION_DURATION,
+};
 
-use super::events::Event;
+use super::{
+    events::Event,
+    scene_registry::SceneRegistry,
+    terminal::Terminal,
+    transition::{Transition, TransitionKind},
+};
 
+const BACKDROP_DIMMING: f32 = 0.35;
+
+/// Keeps a stack of scenes, where only the scene on top is updated. Scenes are built from the
+/// registry the first time they are needed and kept afterwards.
 pub struct SceneManager {
-    scenes: HashMap<String, Box<dyn Scene>>,
-    current_scene: Option<Box<dyn Scene>>,
+    registry: SceneRegistry,
+    scenes: HashMap<SceneId, Box<dyn Scene>>,
+    stack: Vec<SceneId>,
+    transition: Option<Transition>,
+    width: u16,
+    height: u16,
     exit: bool,
 }
 
 impl SceneManager {
-    pub fn new() -> SceneManager {
+    pub fn new(registry: SceneRegistry, width: u16, height: u16) -> SceneManager {
         return SceneManager {
+            registry,
             scenes: HashMap::new(),
-            current_scene: None,
+            stack: Vec::new(),
+            transition: None,
+            width,
+            height,
             exit: false,
         };
     }
 
-    pub fn current_scene_mut(&mut self) -> &mut Option<Box<dyn Scene>> {
-        return &mut self.current_scene;
-    }
+    pub fn current_scene_mut(&mut self) -> Option<&mut Box<dyn Scene>> {
+        let id = self.stack.last()?;
 
-    pub fn set_current_scene(&mut self, name: &str) {
-        if let Some(previous_scene) = self.current_scene.take() {
-            let previous_name = previous_scene.name();
-            self.scenes.insert(previous_name, previous_scene);
-        }
-
-        self.current_scene = self.scenes.remove(name);
-        self.current_scene.as_mut().unwrap().render();
+        return self.scenes.get_mut(id);
     }
 
     pub fn exit(&self) -> bool {
         return self.exit;
     }
 
-    pub fn add_scene(&mut self, scene: Box<dyn Scene>) {
-        self.scenes.insert(scene.name(), scene);
+    /// Puts the scene on top of the current one.
+    pub fn push_scene(&mut self, id: SceneId, event: Event) {
+        let backdrop = self
+            .current_scene_mut()
+            .map(|scene| scene.cell_matrix().presented().dimmed(BACKDROP_DIMMING));
+
+        self.stack.push(id);
+        self.enter_current_scene(event, backdrop);
+    }
+
+    /// Removes the current scene, going back to the one below it.
+    pub fn pop_scene(&mut self, event: Event) {
+        self.stack.pop();
+        self.enter_current_scene(event, None);
+    }
+
+    /// Drops the whole stack and starts over from the scene.
+    pub fn reset_scenes(&mut self, id: SceneId, event: Event) {
+        self.stack.clear();
+        self.push_scene(id, event);
+    }
+
+    /// Advances the running transition, if any. Returns false when there is nothing to animate and
+    /// the current scene should be updated instead.
+    pub fn update_transition(&mut self, frame_duration: f64, terminal: &mut Terminal) -> bool {
+        let Some(transition) = self.transition.as_mut() else {
+            return false;
+        };
+
+        let running = transition.update(frame_duration);
+
+        transition.write(terminal);
+
+        if !running {
+            self.transition = None;
+        }
+
+        return true;
     }
 
     pub fn handle_update_result(&mut self, event: Event) {
-        match event {
-            Event::Start => {
-                self.start_new_game();
-                self.set_current_scene("gameplay");
+        let transition_kind = match event {
+            Event::Start { .. } | Event::Restart | Event::Continue => TransitionKind::Wipe,
+            Event::Pause | Event::Resume | Event::SaveFailed | Event::ThemeChanged => {
+                TransitionKind::Fade
             }
-            Event::Pause => {
-                self.set_current_scene("paused");
+            Event::End { .. } | Event::Abandon | Event::SaveAndQuit => TransitionKind::Dissolve,
+            Event::GoToMenu | Event::ShowStatistics | Event::ShowSettings => TransitionKind::Slide,
+            Event::Exit | Event::ValueChanged | Event::Dismiss | Event::None => {
+                self.process_event(event);
+                return;
             }
-            Event::Resume => {
-                self.set_current_scene("gameplay");
+        };
+
+        let from = self
+            .current_scene_mut()
+            .map(|scene| scene.cell_matrix().presented());
+
+        self.process_event(event);
+
+        let to = self
+            .current_scene_mut()
+            .map(|scene| scene.cell_matrix().presented());
+
+        if let (Some(from), Some(to)) = (from, to) {
+            self.transition = Some(Transition::new(
+                transition_kind,
+                TRANSITION_DURATION,
+                from,
+                to,
+            ));
+        }
+    }
+
+    fn process_event(&mut self, event: Event) {
+        match event {
+            Event::Start { .. } | Event::Restart | Event::Continue => {
+                self.reset_scenes(SceneId::Gameplay, event);
+            }
+            Event::Pause | Event::SaveFailed => {
+                self.push_scene(SceneId::Paused, event);
             }
-            Event::Restart => {
-                self.start_new_game();
-                self.set_current_scene("gameplay");
+            Event::Resume | Event::Abandon | Event::SaveAndQuit => {
+                self.pop_scene(event);
             }
-            Event::End => {
-                self.update_game_over_scene();
-                self.set_current_scene("game_over");
+            Event::End { .. } => {
+                self.push_scene(SceneId::GameOver, event);
             }
             Event::GoToMenu => {
-                self.set_current_scene("main_menu");
+                self.reset_scenes(SceneId::MainMenu, event);
+            }
+            Event::ShowStatistics => {
+                self.reset_scenes(SceneId::Statistics, event);
+            }
+            Event::ShowSettings => {
+                self.reset_scenes(SceneId::Settings, event);
+            }
+            Event::ThemeChanged => {
+                if let Some(&id) = self.stack.last() {
+                    self.scenes.clear();
+                    self.reset_scenes(id, event);
+                }
             }
             Event::Exit => {
                 self.exit = true;
             }
-            Event::None => {}
+            Event::ValueChanged | Event::Dismiss | Event::None => {}
         }
     }
 
-    fn start_new_game(&mut self) {
-        self.scenes
-            .get_mut("gameplay")
-            .unwrap()
-            .as_any_mut()
-            .downcast_mut::<GameplayScene>()
-            .unwrap()
-            .start_new_game();
-    }
+    fn enter_current_scene(&mut self, event: Event, backdrop: Option<CellMatrix>) {
+        let Some(&id) = self.stack.last() else {
+            return;
+        };
 
-    fn update_game_over_scene(&mut self) {
-        let score = if let Some(scene) = self.scenes.get_mut("gameplay") {
-            scene
-                .as_any_mut()
-                .downcast_mut::<GameplayScene>()
-                .unwrap()
-                .score()
-        } else if let Some(scene) = self.current_scene.as_mut() {
-            scene
-                .as_any_mut()
-                .downcast_mut::<GameplayScene>()
-                .unwrap()
-                .score()
-        } else {
-            0
+        let scene = match self.scenes.entry(id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => match self.registry.build(id, self.width, self.height) {
+                Some(scene) => entry.insert(scene),
+                None => {
+                    self.stack.pop();
+                    return;
+                }
+            },
         };
 
-        self.scenes
-            .get_mut("game_over")
-            .unwrap()
-            .set_text_string("score", format!("{:010}", score));
+        if let Some(backdrop) = backdrop.filter(|_| scene.is_overlay()) {
+            scene.set_backdrop(backdrop);
+        }
+
+        let follow_up = scene.enter(&event);
+
+        scene.render();
+
+        self.process_event(follow_up);
     }
 }
@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+use crate::scenes::scene::{Scene, SceneId};
+
+pub type SceneBuilder = fn(u16, u16) -> Box<dyn Scene>;
+
+pub struct SceneRegistry {
+    builders: HashMap<SceneId, SceneBuilder>,
+}
+
+impl SceneRegistry {
+    pub fn new() -> SceneRegistry {
+        return SceneRegistry {
+            builders: HashMap::new(),
+        };
+    }
+
+    pub fn register(&mut self, id: SceneId, builder: SceneBuilder) {
+        self.builders.insert(id, builder);
+    }
+
+    pub fn build(&self, id: SceneId, width: u16, height: u16) -> Option<Box<dyn Scene>> {
+        return self.builders.get(&id).map(|builder| builder(width, height));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    COUNTDOWN, DEATH_REPLAY, EVEN_SPEED, HALF_BLOCKS, INITIAL_SNAKE_LENGTH, MOUSE_STEERING,
+    SHAPE_CODING, SKIN, SNAKE_SPEED, THEME,
+};
+
+use super::storage::Record;
+
+const SETTINGS_FILE: &str = "settings";
+
+pub const STARTING_LENGTHS: [u16; 3] = [INITIAL_SNAKE_LENGTH, 5, 10];
+pub const COUNTDOWNS: [u16; 4] = [0, 1, COUNTDOWN, 5];
+
+/// User preferences, kept across sessions.
+pub struct Settings {
+    seed: Option<u64>,
+    snake_speed: f32,
+    starting_length: u16,
+    death_replay: bool,
+    mouse_steering: bool,
+    theme: String,
+    shape_coding: bool,
+    half_blocks: bool,
+    even_speed: bool,
+    countdown: u16,
+    skin: String,
+}
+
+impl Settings {
+    pub fn load() -> Settings {
+        let record = Record::load(SETTINGS_FILE).unwrap_or(Record::new());
+
+        return Settings {
+            seed: record.get_parsed("seed"),
+            snake_speed: record.get_parsed("snake_speed").unwrap_or(SNAKE_SPEED),
+            starting_length: record
+                .get_parsed("starting_length")
+                .unwrap_or(INITIAL_SNAKE_LENGTH),
+            death_replay: record.get_parsed("death_replay").unwrap_or(DEATH_REPLAY),
+            mouse_steering: record
+                .get_parsed("mouse_steering")
+                .unwrap_or(MOUSE_STEERING),
+            theme: record.get("theme").unwrap_or(THEME).to_string(),
+            shape_coding: record.get_parsed("shape_coding").unwrap_or(SHAPE_CODING),
+            half_blocks: record.get_parsed("half_blocks").unwrap_or(HALF_BLOCKS),
+            even_speed: record.get_parsed("even_speed").unwrap_or(EVEN_SPEED),
+            countdown: record.get_parsed("countdown").unwrap_or(COUNTDOWN),
+            skin: record.get("skin").unwrap_or(SKIN).to_string(),
+        };
+    }
+
+    pub fn save(&self) {
+        let mut record = Record::new();
+
+        if let Some(seed) = self.seed {
+            record.set("seed", seed.to_string());
+        }
+
+        record.set("snake_speed", self.snake_speed.to_string());
+        record.set("starting_length", self.starting_length.to_string());
+        record.set("death_replay", self.death_replay.to_string());
+        record.set("mouse_steering", self.mouse_steering.to_string());
+        record.set("theme", self.theme.clone());
+        record.set("shape_coding", self.shape_coding.to_string());
+        record.set("half_blocks", self.half_blocks.to_string());
+        record.set("even_speed", self.even_speed.to_string());
+        record.set("countdown", self.countdown.to_string());
+        record.set("skin", self.skin.clone());
+
+        let _ = record.save(SETTINGS_FILE);
+    }
+
+    /// Games started with a seed always have the same layout and fruit sequence.
+    pub fn seed(&self) -> Option<u64> {
+        return self.seed;
+    }
+
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    /// Cells per second.
+    pub fn snake_speed(&self) -> f32 {
+        return self.snake_speed;
+    }
+
+    pub fn set_snake_speed(&mut self, snake_speed: f32) {
+        self.snake_speed = snake_speed;
+    }
+
+    pub fn starting_length(&self) -> u16 {
+        return self.starting_length;
+    }
+
+    pub fn set_starting_length(&mut self, starting_length: u16) {
+        self.starting_length = starting_length;
+    }
+
+    /// Whether the last seconds before a death are replayed before the game over screen.
+    pub fn death_replay(&self) -> bool {
+        return self.death_replay;
+    }
+
+    pub fn set_death_replay(&mut self, death_replay: bool) {
+        self.death_replay = death_replay;
+    }
+
+    /// Whether clicking beside the snake's head turns it towards the click.
+    pub fn mouse_steering(&self) -> bool {
+        return self.mouse_steering;
+    }
+
+    pub fn set_mouse_steering(&mut self, mouse_steering: bool) {
+        self.mouse_steering = mouse_steering;
+    }
+
+    /// Name of a bundled theme or of a file in the themes directory.
+    pub fn theme(&self) -> String {
+        return self.theme.clone();
+    }
+
+    pub fn set_theme(&mut self, theme: String) {
+        self.theme = theme;
+    }
+
+    /// Whether the snake's head, the fruit and the gates get shapes that don't rely on their
+    /// colors.
+    pub fn shape_coding(&self) -> bool {
+        return self.shape_coding;
+    }
+
+    pub fn set_shape_coding(&mut self, shape_coding: bool) {
+        self.shape_coding = shape_coding;
+    }
+
+    /// Whether the arena is drawn with half blocks, which makes its cells square.
+    pub fn half_blocks(&self) -> bool {
+        return self.half_blocks;
+    }
+
+    pub fn set_half_blocks(&mut self, half_blocks: bool) {
+        self.half_blocks = half_blocks;
+    }
+
+    /// Whether vertical steps take longer than horizontal ones when the cells are taller than
+    /// wide, so the snake looks as fast in every direction.
+    pub fn even_speed(&self) -> bool {
+        return self.even_speed;
+    }
+
+    pub fn set_even_speed(&mut self, even_speed: bool) {
+        self.even_speed = even_speed;
+    }
+
+    /// Seconds counted down before the snake starts moving, on start and when resuming. There is
+    /// no countdown when it is zero.
+    pub fn countdown(&self) -> u16 {
+        return self.countdown;
+    }
+
+    pub fn set_countdown(&mut self, countdown: u16) {
+        self.countdown = countdown;
+    }
+
+    /// Id of the snake's skin.
+    pub fn skin(&self) -> String {
+        return self.skin.clone();
+    }
+
+    pub fn set_skin(&mut self, skin: String) {
+        self.skin = skin;
+    }
+}
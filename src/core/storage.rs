@@ -0,0 +1,220 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use crate::cells::vector::Vector;
+
+/// Ordered set of `key=value` entries persisted as a plain text file in the data directory.
+pub struct Record {
+    entries: Vec<(String, String)>,
+}
+
+impl Record {
+    pub fn new() -> Record {
+        return Record {
+            entries: Vec::new(),
+        };
+    }
+
+    pub fn load(file_name: &str) -> Option<Record> {
+        let content = fs::read_to_string(file_path(file_name)?).ok()?;
+
+        return Some(Record::parse(&content));
+    }
+
+    /// Reads the entries from the content of a record file. Lines without a `=` are ignored.
+    pub fn parse(content: &str) -> Record {
+        let mut record = Record::new();
+
+        for line in content.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                record.set(key.trim(), value.trim().to_string());
+            }
+        }
+
+        return record;
+    }
+
+    /// Returns the names of the files in a directory of the data directory, without the extension.
+    pub fn file_names(directory: &str, extension: &str) -> Vec<String> {
+        let Some(entries) = file_path(directory).and_then(|path| fs::read_dir(path).ok()) else {
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|path_extension| path_extension == extension)
+            })
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+            .collect();
+
+        names.sort();
+
+        return names;
+    }
+
+    pub fn save(&self, file_name: &str) -> io::Result<()> {
+        let path = file_path(file_name).ok_or(io::ErrorKind::NotFound)?;
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+
+        return fs::File::create(path)?.write_all(self.content().as_bytes());
+    }
+
+    /// The entries as they are written to the file, one per line.
+    pub fn content(&self) -> String {
+        return self
+            .entries
+            .iter()
+            .map(|(key, value)| format!("{}={}\n", key, value))
+            .collect();
+    }
+
+    pub fn exists(file_name: &str) -> bool {
+        return file_path(file_name).is_some_and(|path| path.is_file());
+    }
+
+    pub fn remove(file_name: &str) {
+        if let Some(path) = file_path(file_name) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        return self
+            .entries
+            .iter()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value.as_str());
+    }
+
+    pub fn get_parsed<T: FromStr>(&self, key: &str) -> Option<T> {
+        return self.get(key)?.parse().ok();
+    }
+
+    pub fn get_positions(&self, key: &str) -> Option<Vec<Vector<u16>>> {
+        let value = self.get(key)?;
+
+        if value.is_empty() {
+            return Some(Vec::new());
+        }
+
+        return value
+            .split(';')
+            .map(|position| {
+                let (x, y) = position.split_once(',')?;
+
+                return Some(Vector::<u16>::new(x.parse().ok()?, y.parse().ok()?));
+            })
+            .collect();
+    }
+
+    pub fn set(&mut self, key: &str, value: String) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|(entry_key, _)| entry_key == key)
+        {
+            Some((_, entry_value)) => *entry_value = value,
+            None => self.entries.push((key.to_string(), value)),
+        }
+    }
+
+    pub fn set_positions(&mut self, key: &str, positions: &[Vector<u16>]) {
+        self.set(
+            key,
+            positions
+                .iter()
+                .map(|position| format!("{},{}", position.x(), position.y()))
+                .collect::<Vec<String>>()
+                .join(";"),
+        );
+    }
+}
+
+/// Path of a file in the data directory.
+pub fn file_path(file_name: &str) -> Option<PathBuf> {
+    let data_directory = match env::var_os("XDG_DATA_HOME") {
+        Some(directory) if !directory.is_empty() => PathBuf::from(directory),
+        _ => PathBuf::from(env::var_os("HOME")?)
+            .join(".local")
+            .join("share"),
+    };
+
+    return Some(data_directory.join("snake").join(file_name));
+}
+
+/// Whether a name picked by the player can be part of a file name, which keeps names from the
+/// settings from reaching outside of their directory.
+pub fn is_valid_name(name: &str) -> bool {
+    return !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_survive_a_round_trip() {
+        let mut record = Record::new();
+
+        record.set("score", 42.to_string());
+        record.set("mode", "portals".to_string());
+        record.set("score", 43.to_string());
+        record.set_positions(
+            "snake_body",
+            &[Vector::<u16>::new(3, 4), Vector::<u16>::new(3, 5)],
+        );
+        record.set_positions("fruit", &[]);
+
+        let parsed = Record::parse(&record.content());
+
+        assert_eq!(record.content(), parsed.content());
+        assert_eq!(parsed.get_parsed::<u32>("score"), Some(43));
+        assert_eq!(parsed.get("mode"), Some("portals"));
+        assert_eq!(
+            parsed.get_positions("snake_body"),
+            Some(vec![Vector::<u16>::new(3, 4), Vector::<u16>::new(3, 5)])
+        );
+        assert_eq!(parsed.get_positions("fruit"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn parsing_trims_entries_and_skips_other_lines() {
+        let record = Record::parse("# comment\n speed = 12.5 \n\nmode=classic\n");
+
+        assert_eq!(record.get_parsed::<f32>("speed"), Some(12.5));
+        assert_eq!(record.get("mode"), Some("classic"));
+        assert_eq!(record.get("# comment"), None);
+    }
+
+    #[test]
+    fn invalid_values_are_missing() {
+        let record = Record::parse("score=many\nportals=1,2;3\n");
+
+        assert_eq!(record.get_parsed::<u32>("score"), None);
+        assert_eq!(record.get_positions("portals"), None);
+        assert_eq!(record.get("missing"), None);
+    }
+
+    #[test]
+    fn only_simple_names_are_valid() {
+        assert!(is_valid_name("high-contrast"));
+        assert!(is_valid_name("theme2"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("../settings"));
+        assert!(!is_valid_name("/etc/passwd"));
+        assert!(!is_valid_name("Classic"));
+    }
+}
@@ -1,20 +1,30 @@
-use std::io::{self, stdout, Read, Write};
+use std::{
+    collections::VecDeque,
+    io::{self, stdout, Read, Write},
+};
 
 use termion::{
     async_stdin, cursor,
-    event::Key,
-    input::TermRead,
     raw::{IntoRawMode, RawTerminal},
     terminal_size, AsyncReader,
 };
 
 use crate::{MINIMUM_HEIGHT, MINIMUM_WIDTH};
 
+use super::input::{parsed_inputs, Input, MouseInput};
+
+// Reports every mouse event, including motion without a pressed button, in the SGR format
+const ENABLE_MOUSE: &str = "\x1b[?1003h\x1b[?1006h";
+const DISABLE_MOUSE: &str = "\x1b[?1006l\x1b[?1003l";
+
 pub struct Terminal {
     width: u16,
     height: u16,
     stdin: AsyncReader,
     stdout: RawTerminal<io::Stdout>,
+    inputs: VecDeque<Input>,
+    /// Start of a sequence that was cut off at the end of the last read.
+    unparsed_bytes: Vec<u8>,
 }
 
 impl Terminal {
@@ -32,12 +42,19 @@ impl Terminal {
             );
         }
 
-        return Terminal {
+        let mut terminal = Terminal {
             width: terminal_size.0,
             height: terminal_size.1,
             stdin,
             stdout,
+            inputs: VecDeque::new(),
+            unparsed_bytes: Vec::new(),
         };
+
+        terminal.write(ENABLE_MOUSE);
+        terminal.flush();
+
+        return terminal;
     }
 
     pub fn width(&self) -> u16 {
@@ -71,10 +88,31 @@ impl Terminal {
         self.flush();
     }
 
-    pub fn get_pressed_key(&mut self) -> Option<Key> {
-        let keys = self.stdin.by_ref().keys();
+    /// Returns the oldest input that wasn't handled yet. Consecutive pointer moves are merged into
+    /// the last one.
+    pub fn get_input(&mut self) -> Option<Input> {
+        let mut bytes = std::mem::take(&mut self.unparsed_bytes);
+        let unparsed_length = bytes.len();
+        let _ = self.stdin.read_to_end(&mut bytes);
+        let parsed_length = parsed_inputs(&bytes, bytes.len() == unparsed_length, &mut self.inputs);
+
+        self.unparsed_bytes = bytes.split_off(parsed_length);
 
-        // ignores all keys except the first one, also ignores errors
-        return keys.take(1).next().map(|k| k.unwrap());
+        while let (
+            Some(Input::Mouse(MouseInput::Move(_))),
+            Some(Input::Mouse(MouseInput::Move(_))),
+        ) = (self.inputs.front(), self.inputs.get(1))
+        {
+            self.inputs.pop_front();
+        }
+
+        return self.inputs.pop_front();
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        self.write(DISABLE_MOUSE);
+        self.flush();
     }
 }
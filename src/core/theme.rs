@@ -0,0 +1,244 @@
+use std::cell::RefCell;
+
+use termion::color::Rgb;
+
+use crate::cells::color::Color;
+
+use super::{
+    settings::Settings,
+    storage::{is_valid_name, Record},
+};
+
+const THEMES_DIRECTORY: &str = "themes";
+const THEME_EXTENSION: &str = "theme";
+const BUNDLED_THEMES: [(&str, &str); 7] = [
+    ("classic", include_str!("../../themes/classic.theme")),
+    ("solarized", include_str!("../../themes/solarized.theme")),
+    (
+        "high-contrast",
+        include_str!("../../themes/high-contrast.theme"),
+    ),
+    ("monochrome", include_str!("../../themes/monochrome.theme")),
+    // Palettes for the common kinds of color blindness, built around colors they can tell apart
+    ("protanopia", include_str!("../../themes/protanopia.theme")),
+    (
+        "deuteranopia",
+        include_str!("../../themes/deuteranopia.theme"),
+    ),
+    ("tritanopia", include_str!("../../themes/tritanopia.theme")),
+];
+
+thread_local! {
+    static CURRENT_THEME: RefCell<Option<Theme>> = const { RefCell::new(None) };
+}
+
+/// Colors for each role in the game. Themes are `key=value` files with a color per role, either a
+/// name like `light_green` or a hex code like `#80ff80`. Roles missing from a file are taken from
+/// the classic theme.
+#[derive(Clone, Copy)]
+pub struct Theme {
+    background: Rgb,
+    text: Rgb,
+    hud: Rgb,
+    toast: Rgb,
+    wall: Rgb,
+    snake_head: Rgb,
+    snake_body: Rgb,
+    fruit: Rgb,
+    hazard: Rgb,
+    hazard_bar: Rgb,
+    hazard_gate: Rgb,
+    portals: [Rgb; 2],
+    danger: Rgb,
+    menu: Rgb,
+    paused: Rgb,
+    game_over: Rgb,
+    statistics: Rgb,
+    settings: Rgb,
+}
+
+impl Theme {
+    /// Loads a bundled theme or one from the themes directory, falling back to the classic theme
+    /// if there is neither or the name isn't valid.
+    pub fn load(name: &str) -> Theme {
+        let record = match BUNDLED_THEMES.iter().find(|(bundled, _)| *bundled == name) {
+            Some((_, content)) => Record::parse(content),
+            None if is_valid_name(name) => Record::load(&format!(
+                "{}/{}.{}",
+                THEMES_DIRECTORY, name, THEME_EXTENSION
+            ))
+            .unwrap_or(Record::new()),
+            None => Record::new(),
+        };
+
+        return Theme::from_record(&record);
+    }
+
+    /// The bundled themes followed by the ones in the themes directory.
+    pub fn names() -> Vec<String> {
+        let mut names: Vec<String> = BUNDLED_THEMES
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        for name in Record::file_names(THEMES_DIRECTORY, THEME_EXTENSION) {
+            // Files whose names couldn't be loaded back are left out
+            if is_valid_name(&name) && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        return names;
+    }
+
+    /// The theme everything is drawn with, loaded from the settings the first time.
+    pub fn current() -> Theme {
+        return CURRENT_THEME.with(|current| {
+            return *current
+                .borrow_mut()
+                .get_or_insert_with(|| Theme::load(&Settings::load().theme()));
+        });
+    }
+
+    /// Scenes that were already built keep the colors of the previous theme.
+    pub fn set_current(theme: Theme) {
+        CURRENT_THEME.with(|current| *current.borrow_mut() = Some(theme));
+    }
+
+    pub fn background(&self) -> Rgb {
+        return self.background;
+    }
+
+    pub fn text(&self) -> Rgb {
+        return self.text;
+    }
+
+    pub fn hud(&self) -> Rgb {
+        return self.hud;
+    }
+
+    pub fn toast(&self) -> Rgb {
+        return self.toast;
+    }
+
+    pub fn wall(&self) -> Rgb {
+        return self.wall;
+    }
+
+    pub fn snake_head(&self) -> Rgb {
+        return self.snake_head;
+    }
+
+    pub fn snake_body(&self) -> Rgb {
+        return self.snake_body;
+    }
+
+    pub fn fruit(&self) -> Rgb {
+        return self.fruit;
+    }
+
+    pub fn hazard(&self) -> Rgb {
+        return self.hazard;
+    }
+
+    pub fn hazard_bar(&self) -> Rgb {
+        return self.hazard_bar;
+    }
+
+    pub fn hazard_gate(&self) -> Rgb {
+        return self.hazard_gate;
+    }
+
+    /// One color for each pair of portals.
+    pub fn portals(&self) -> [Rgb; 2] {
+        return self.portals;
+    }
+
+    /// Death flash and collision marker.
+    pub fn danger(&self) -> Rgb {
+        return self.danger;
+    }
+
+    pub fn menu(&self) -> Rgb {
+        return self.menu;
+    }
+
+    pub fn paused(&self) -> Rgb {
+        return self.paused;
+    }
+
+    pub fn game_over(&self) -> Rgb {
+        return self.game_over;
+    }
+
+    pub fn statistics(&self) -> Rgb {
+        return self.statistics;
+    }
+
+    pub fn settings(&self) -> Rgb {
+        return self.settings;
+    }
+
+    fn from_record(record: &Record) -> Theme {
+        let classic = Record::parse(BUNDLED_THEMES[0].1);
+        let color = |role: &str| {
+            return record
+                .get(role)
+                .and_then(parsed_color)
+                .or_else(|| classic.get(role).and_then(parsed_color))
+                .unwrap_or(Color::White.to_rgb());
+        };
+
+        return Theme {
+            background: color("background"),
+            text: color("text"),
+            hud: color("hud"),
+            toast: color("toast"),
+            wall: color("wall"),
+            snake_head: color("snake_head"),
+            snake_body: color("snake_body"),
+            fruit: color("fruit"),
+            hazard: color("hazard"),
+            hazard_bar: color("hazard_bar"),
+            hazard_gate: color("hazard_gate"),
+            portals: [color("portal_1"), color("portal_2")],
+            danger: color("danger"),
+            menu: color("menu"),
+            paused: color("paused"),
+            game_over: color("game_over"),
+            statistics: color("statistics"),
+            settings: color("settings"),
+        };
+    }
+}
+
+fn parsed_color(value: &str) -> Option<Rgb> {
+    let Some(hex) = value.strip_prefix('#') else {
+        return Color::from_name(value).map(|color| color.to_rgb());
+    };
+
+    if hex.len() != 6 {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+
+    return Some(Rgb(channel(0)?, channel(2)?, channel(4)?));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Theme;
+
+    #[test]
+    fn invalid_names_fall_back_to_the_classic_theme() {
+        let classic = Theme::load("classic");
+
+        for name in ["../settings", "/etc/passwd", "Solarized"] {
+            let theme = Theme::load(name);
+
+            assert!(theme.background() == classic.background());
+            assert!(theme.snake_head() == classic.snake_head());
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::cells::{cell_matrix::CellMatrix, vector::Vector};
+
+use super::terminal::Terminal;
+
+#[derive(Clone, Copy)]
+pub enum TransitionKind {
+    Wipe,
+    Fade,
+    Dissolve,
+    Slide,
+}
+
+/// Blends the outgoing scene into the incoming one over a fixed duration.
+pub struct Transition {
+    kind: TransitionKind,
+    duration: f64,
+    elapsed: f64,
+    from: CellMatrix,
+    to: CellMatrix,
+    output: CellMatrix,
+}
+
+impl Transition {
+    pub fn new(
+        kind: TransitionKind,
+        duration: f64,
+        from: CellMatrix,
+        to: CellMatrix,
+    ) -> Transition {
+        let output = from.snapshot();
+
+        return Transition {
+            kind,
+            duration,
+            elapsed: 0.0,
+            from,
+            to,
+            output,
+        };
+    }
+
+    /// Returns false once the transition is over.
+    pub fn update(&mut self, frame_duration: f64) -> bool {
+        self.elapsed += frame_duration;
+
+        let progress = if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0) as f32
+        } else {
+            1.0
+        };
+
+        let width = self.output.width();
+        let height = self.output.height();
+
+        for y in 0..height {
+            for x in 0..width {
+                let position = Vector::<u16>::new(x, y);
+                let cell = match self.kind {
+                    TransitionKind::Wipe => if (x as f32) < progress * width as f32 {
+                        self.to.get_cell(&position)
+                    } else {
+                        self.from.get_cell(&position)
+                    }
+                    .cloned(),
+                    TransitionKind::Fade => self
+                        .from
+                        .get_cell(&position)
+                        .zip(self.to.get_cell(&position))
+                        .map(|(from, to)| from.interpolated(to, progress)),
+                    TransitionKind::Dissolve => if dissolve_threshold(x, y) < progress {
+                        self.to.get_cell(&position)
+                    } else {
+                        self.from.get_cell(&position)
+                    }
+                    .cloned(),
+                    TransitionKind::Slide => {
+                        // The outgoing scene leaves through the left while the incoming one enters from the right
+                        let shift = (progress * width as f32) as u16;
+
+                        if x + shift < width {
+                            self.from.get_cell(&Vector::<u16>::new(x + shift, y))
+                        } else {
+                            self.to.get_cell(&Vector::<u16>::new(x + shift - width, y))
+                        }
+                        .cloned()
+                    }
+                };
+
+                if let Some(cell) = cell {
+                    if self.output.get_cell(&position) != Some(&cell) {
+                        self.output.set_cell(&position, cell);
+                    }
+                }
+            }
+        }
+
+        return progress < 1.0;
+    }
+
+    pub fn write(&mut self, terminal: &mut Terminal) {
+        self.output.write(terminal);
+    }
+}
+
+/// Pseudo-random value between 0 and 1 for each position, so cells dissolve in a scattered order.
+fn dissolve_threshold(x: u16, y: u16) -> f32 {
+    let mut hash = (x as u32).wrapping_mul(0x9E37_79B1) ^ (y as u32).wrapping_mul(0x85EB_CA77);
+
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x2C1B_3C6D);
+    hash ^= hash >> 12;
+
+    return (hash % 1024) as f32 / 1024.0;
+}
@@ -0,0 +1,156 @@
+use crate::core::storage::Record;
+
+use super::statistics::Statistics;
+
+const ACHIEVEMENTS_FILE: &str = "achievements";
+
+enum Condition {
+    Length(u32),
+    Fill(f32),
+    Survive(f64),
+    GamesPlayed(u32),
+    TotalFruit(u32),
+}
+
+pub struct Achievement {
+    id: &'static str,
+    title: &'static str,
+    description: &'static str,
+    condition: Condition,
+}
+
+impl Achievement {
+    pub fn id(&self) -> &'static str {
+        return self.id;
+    }
+
+    pub fn title(&self) -> &'static str {
+        return self.title;
+    }
+
+    pub fn description(&self) -> &'static str {
+        return self.description;
+    }
+}
+
+pub const ACHIEVEMENTS: [Achievement; 8] = [
+    Achievement {
+        id: "first_bite",
+        title: "First Bite",
+        description: "Eat your first fruit",
+        condition: Condition::TotalFruit(1),
+    },
+    Achievement {
+        id: "growing",
+        title: "Growing Up",
+        description: "Reach length 25",
+        condition: Condition::Length(25),
+    },
+    Achievement {
+        id: "long_snake",
+        title: "Long Snake",
+        description: "Reach length 100",
+        condition: Condition::Length(100),
+    },
+    Achievement {
+        id: "half_full",
+        title: "Half Full",
+        description: "Fill 50% of the arena",
+        condition: Condition::Fill(0.5),
+    },
+    Achievement {
+        id: "survivor",
+        title: "Survivor",
+        description: "Survive 5 minutes",
+        condition: Condition::Survive(300.0),
+    },
+    Achievement {
+        id: "regular",
+        title: "Regular",
+        description: "Play 10 games",
+        condition: Condition::GamesPlayed(10),
+    },
+    Achievement {
+        id: "veteran",
+        title: "Veteran",
+        description: "Play 100 games",
+        condition: Condition::GamesPlayed(100),
+    },
+    Achievement {
+        id: "glutton",
+        title: "Glutton",
+        description: "Eat 500 fruits in total",
+        condition: Condition::TotalFruit(500),
+    },
+];
+
+pub struct Achievements {
+    unlocked: Vec<String>,
+}
+
+impl Achievements {
+    pub fn load() -> Achievements {
+        let unlocked = match Record::load(ACHIEVEMENTS_FILE) {
+            Some(record) => record
+                .get("unlocked")
+                .unwrap_or("")
+                .split(',')
+                .filter(|id| !id.is_empty())
+                .map(|id| id.to_string())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        return Achievements { unlocked };
+    }
+
+    pub fn save(&self) {
+        let mut record = Record::new();
+
+        record.set("unlocked", self.unlocked.join(","));
+
+        let _ = record.save(ACHIEVEMENTS_FILE);
+    }
+
+    pub fn is_unlocked(&self, achievement: &Achievement) -> bool {
+        return self.unlocked.iter().any(|id| id == achievement.id);
+    }
+
+    /// Unlocks and returns the achievements whose conditions are met by the current game. The fruit
+    /// eaten in the current game should not be counted in the statistics yet.
+    pub fn check(
+        &mut self,
+        statistics: &Statistics,
+        fruit: u32,
+        length: u32,
+        fill_ratio: f32,
+        play_time: f64,
+    ) -> Vec<&'static Achievement> {
+        let mut newly_unlocked = Vec::new();
+
+        for achievement in ACHIEVEMENTS.iter() {
+            if self.is_unlocked(achievement) {
+                continue;
+            }
+
+            let met = match achievement.condition {
+                Condition::Length(target) => length >= target,
+                Condition::Fill(target) => fill_ratio >= target,
+                Condition::Survive(target) => play_time >= target,
+                Condition::GamesPlayed(target) => statistics.games_played() >= target,
+                Condition::TotalFruit(target) => statistics.total_fruit() + fruit >= target,
+            };
+
+            if met {
+                self.unlocked.push(achievement.id.to_string());
+                newly_unlocked.push(achievement);
+            }
+        }
+
+        if !newly_unlocked.is_empty() {
+            self.save();
+        }
+
+        return newly_unlocked;
+    }
+}
@@ -0,0 +1,34 @@
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DeathCause {
+    Wall,
+    Collision,
+    Hazard,
+    Quit,
+}
+
+impl DeathCause {
+    pub const ALL: [DeathCause; 4] = [
+        DeathCause::Wall,
+        DeathCause::Collision,
+        DeathCause::Hazard,
+        DeathCause::Quit,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            DeathCause::Wall => "wall",
+            DeathCause::Collision => "collision",
+            DeathCause::Hazard => "hazard",
+            DeathCause::Quit => "quit",
+        };
+    }
+
+    pub fn description(&self) -> &'static str {
+        return match self {
+            DeathCause::Wall => "Hit a wall",
+            DeathCause::Collision => "Bit itself",
+            DeathCause::Hazard => "Hit a hazard",
+            DeathCause::Quit => "Gave up",
+        };
+    }
+}
@@ -1,14 +1,18 @@
-use rand::{rng, Rng};
-
-use crate::cells::{
-    cell::{Cell, CellType},
-    cell_group::CellGroup,
-    cell_matrix::CellMatrix,
-    color::Color,
-    vector::Vector,
+use crate::{
+    cells::{
+        cell::{Cell, CellType},
+        cell_group::CellGroup,
+        cell_matrix::CellMatrix,
+        glyph::Glyph,
+        vector::Vector,
+    },
+    core::{random::Random, theme::Theme},
 };
 
+use super::{hazard::Hazards, portal::Portals};
+
 pub struct Fruit {
+    position: Option<Vector<u16>>,
     cell_group: CellGroup,
 }
 
@@ -17,14 +21,13 @@ impl Fruit {
         cell_matrix: &CellMatrix,
         gameplay_area_origin: &Vector<u16>,
         gameplay_area_extension: &Vector<u16>,
+        hazards: &Hazards,
+        portals: &Portals,
+        random: &mut Random,
     ) -> Fruit {
-        let position;
-        let mut cell_group = CellGroup::new();
-
         loop {
-            let mut rng = rng();
-            let x = rng.random_range(gameplay_area_origin.x()..gameplay_area_extension.x());
-            let y = rng.random_range(gameplay_area_origin.y()..gameplay_area_extension.y());
+            let x = random.random_range(gameplay_area_origin.x()..gameplay_area_extension.x());
+            let y = random.random_range(gameplay_area_origin.y()..gameplay_area_extension.y());
 
             let possible_position = Vector::<u16>::new(x, y);
 
@@ -33,30 +36,45 @@ impl Fruit {
                 .unwrap()
                 .cell_type()
                 == CellType::Empty
+                && !hazards.covers(&possible_position)
+                && !portals.covers(&possible_position)
             {
-                position = possible_position;
-                cell_group.set_cell(
-                    position.clone(),
-                    Cell::new(
-                        '■',
-                        Color::Black.to_rgb(),
-                        Color::Red.to_rgb(),
-                        CellType::Fruit,
-                    ),
-                );
-                break;
+                return Fruit::at(possible_position);
             }
         }
+    }
+
+    pub fn at(position: Vector<u16>) -> Fruit {
+        let theme = Theme::current();
+        let mut cell_group = CellGroup::new();
 
-        return Fruit { cell_group };
+        cell_group.set_cell(
+            position.clone(),
+            Cell::new(
+                Glyph::Fruit.char(),
+                theme.background(),
+                theme.fruit(),
+                CellType::Fruit,
+            ),
+        );
+
+        return Fruit {
+            position: Some(position),
+            cell_group,
+        };
     }
 
     pub fn none() -> Fruit {
         return Fruit {
+            position: None,
             cell_group: CellGroup::new(),
         };
     }
 
+    pub fn position(&self) -> Option<Vector<u16>> {
+        return self.position.clone();
+    }
+
     pub fn render(&mut self, cell_matrix: &mut CellMatrix) {
         self.cell_group.render(cell_matrix);
     }
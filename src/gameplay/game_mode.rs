@@ -0,0 +1,25 @@
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    Classic,
+    Hazards,
+    Portals,
+}
+
+impl GameMode {
+    pub fn from_name(name: &str) -> Option<GameMode> {
+        return match name {
+            "classic" => Some(GameMode::Classic),
+            "hazards" => Some(GameMode::Hazards),
+            "portals" => Some(GameMode::Portals),
+            _ => None,
+        };
+    }
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            GameMode::Classic => "classic",
+            GameMode::Hazards => "hazards",
+            GameMode::Portals => "portals",
+        };
+    }
+}
@@ -0,0 +1,335 @@
+use crate::{
+    cells::{
+        cell::{Cell, CellType},
+        cell_group::CellGroup,
+        cell_matrix::CellMatrix,
+        glyph::Glyph,
+        vector::Vector,
+    },
+    core::theme::Theme,
+};
+
+use super::snake::Direction;
+
+const BAR_RADIUS: u16 = 3;
+const BAR_ROTATION_PERIOD: u32 = 4;
+const BAR_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (1, 1), (0, 1), (-1, 1)];
+const GATE_LENGTH: u16 = 9;
+const GATE_TOGGLE_PERIOD: u32 = 20;
+
+enum Hazard {
+    Patroller {
+        position: Vector<u16>,
+        direction: Direction,
+    },
+    RotatingBar {
+        pivot: Vector<u16>,
+        phase: usize,
+    },
+    Gate {
+        cells: Vec<Vector<u16>>,
+        closed: bool,
+    },
+}
+
+impl Hazard {
+    fn step(
+        &mut self,
+        ticks: u32,
+        gameplay_area_origin: &Vector<u16>,
+        gameplay_area_extension: &Vector<u16>,
+    ) {
+        match self {
+            Hazard::Patroller {
+                position,
+                direction,
+            } => {
+                // Bounces back when the next cell would be outside the gameplay area, that is, a wall
+                let next = match offset_position(
+                    position,
+                    direction.offset(),
+                    gameplay_area_origin,
+                    gameplay_area_extension,
+                ) {
+                    Some(next) => next,
+                    None => {
+                        *direction = direction.opposite();
+
+                        offset_position(
+                            position,
+                            direction.offset(),
+                            gameplay_area_origin,
+                            gameplay_area_extension,
+                        )
+                        .unwrap_or(position.clone())
+                    }
+                };
+
+                *position = next;
+            }
+            Hazard::RotatingBar { phase, .. } => {
+                if ticks.is_multiple_of(BAR_ROTATION_PERIOD) {
+                    *phase = (*phase + 1) % BAR_DIRECTIONS.len();
+                }
+            }
+            Hazard::Gate { closed, .. } => {
+                if ticks.is_multiple_of(GATE_TOGGLE_PERIOD) {
+                    *closed = !*closed;
+                }
+            }
+        }
+    }
+
+    fn cells(
+        &self,
+        gameplay_area_origin: &Vector<u16>,
+        gameplay_area_extension: &Vector<u16>,
+    ) -> Vec<(Vector<u16>, Cell)> {
+        let theme = Theme::current();
+
+        return match self {
+            Hazard::Patroller { position, .. } => vec![(
+                position.clone(),
+                Cell::new(
+                    Glyph::Patroller.char(),
+                    theme.background(),
+                    theme.hazard(),
+                    CellType::Hazard,
+                ),
+            )],
+            Hazard::RotatingBar { pivot, phase } => {
+                let (x_step, y_step) = BAR_DIRECTIONS[*phase];
+                let radius = BAR_RADIUS as i32;
+
+                (-radius..=radius)
+                    .filter_map(|i| {
+                        offset_position(
+                            pivot,
+                            (x_step * i, y_step * i),
+                            gameplay_area_origin,
+                            gameplay_area_extension,
+                        )
+                    })
+                    .map(|position| {
+                        (
+                            position,
+                            Cell::new(
+                                Glyph::Bar(*phase).char(),
+                                theme.background(),
+                                theme.hazard_bar(),
+                                CellType::Hazard,
+                            ),
+                        )
+                    })
+                    .collect()
+            }
+            Hazard::Gate { cells, closed } => {
+                if !*closed {
+                    return Vec::new();
+                }
+
+                cells
+                    .iter()
+                    .map(|position| {
+                        (
+                            position.clone(),
+                            Cell::new(
+                                Glyph::Gate.char(),
+                                theme.background(),
+                                theme.hazard_gate(),
+                                CellType::Hazard,
+                            ),
+                        )
+                    })
+                    .collect()
+            }
+        };
+    }
+
+    /// Returns true if the hazard can ever reach the position.
+    fn covers(&self, position: &Vector<u16>) -> bool {
+        return match self {
+            Hazard::Patroller {
+                position: patroller_position,
+                direction,
+            } => match direction {
+                Direction::Left | Direction::Right => position.y() == patroller_position.y(),
+                Direction::Up | Direction::Down => position.x() == patroller_position.x(),
+            },
+            Hazard::RotatingBar { pivot, .. } => {
+                position.x().abs_diff(pivot.x()) <= BAR_RADIUS
+                    && position.y().abs_diff(pivot.y()) <= BAR_RADIUS
+            }
+            Hazard::Gate { cells, .. } => cells.contains(position),
+        };
+    }
+}
+
+pub struct Hazards {
+    hazards: Vec<Hazard>,
+    gameplay_area_origin: Vector<u16>,
+    gameplay_area_extension: Vector<u16>,
+    ticks: u32,
+    occupied: Vec<Vector<u16>>,
+    cell_group: CellGroup,
+}
+
+impl Hazards {
+    pub fn new(
+        gameplay_area_origin: &Vector<u16>,
+        gameplay_area_extension: &Vector<u16>,
+    ) -> Hazards {
+        let origin_x = gameplay_area_origin.x();
+        let origin_y = gameplay_area_origin.y();
+        let area_width = gameplay_area_extension.x() - origin_x;
+        let area_height = gameplay_area_extension.y() - origin_y;
+        let gate_x = origin_x + area_width / 2 - GATE_LENGTH / 2;
+
+        let hazards = vec![
+            Hazard::Patroller {
+                position: Vector::<u16>::new(origin_x + area_width / 4, origin_y + area_height / 4),
+                direction: Direction::Right,
+            },
+            Hazard::Patroller {
+                position: Vector::<u16>::new(
+                    origin_x + area_width * 3 / 4,
+                    origin_y + area_height * 3 / 4,
+                ),
+                direction: Direction::Left,
+            },
+            Hazard::RotatingBar {
+                pivot: Vector::<u16>::new(origin_x + area_width / 4, origin_y + area_height / 2),
+                phase: 0,
+            },
+            Hazard::RotatingBar {
+                pivot: Vector::<u16>::new(
+                    origin_x + area_width * 3 / 4,
+                    origin_y + area_height / 2,
+                ),
+                phase: 2,
+            },
+            Hazard::Gate {
+                cells: (0..GATE_LENGTH)
+                    .map(|i| Vector::<u16>::new(gate_x + i, origin_y + area_height / 8))
+                    .collect(),
+                // The snake starts below the top gate heading up, so it starts open
+                closed: false,
+            },
+            Hazard::Gate {
+                cells: (0..GATE_LENGTH)
+                    .map(|i| {
+                        Vector::<u16>::new(gate_x + i, origin_y + area_height - 1 - area_height / 8)
+                    })
+                    .collect(),
+                closed: true,
+            },
+        ];
+
+        let mut hazards = Hazards {
+            hazards,
+            gameplay_area_origin: gameplay_area_origin.clone(),
+            gameplay_area_extension: gameplay_area_extension.clone(),
+            ticks: 0,
+            occupied: Vec::new(),
+            cell_group: CellGroup::new(),
+        };
+
+        hazards.update_cells();
+
+        return hazards;
+    }
+
+    /// Rebuilds the hazards as they were after the given number of ticks.
+    pub fn restore(
+        gameplay_area_origin: &Vector<u16>,
+        gameplay_area_extension: &Vector<u16>,
+        ticks: u32,
+    ) -> Hazards {
+        let mut hazards = Hazards::new(gameplay_area_origin, gameplay_area_extension);
+
+        for _ in 0..ticks {
+            hazards.tick();
+        }
+
+        return hazards;
+    }
+
+    pub fn none() -> Hazards {
+        return Hazards {
+            hazards: Vec::new(),
+            gameplay_area_origin: Vector::<u16>::zero(),
+            gameplay_area_extension: Vector::<u16>::zero(),
+            ticks: 0,
+            occupied: Vec::new(),
+            cell_group: CellGroup::new(),
+        };
+    }
+
+    pub fn ticks(&self) -> u32 {
+        return self.ticks;
+    }
+
+    /// Advances every hazard by one simulation tick.
+    pub fn tick(&mut self) {
+        self.ticks += 1;
+
+        for position in self.occupied.drain(..) {
+            self.cell_group.set_cell(position, Cell::new_empty());
+        }
+
+        for hazard in self.hazards.iter_mut() {
+            hazard.step(
+                self.ticks,
+                &self.gameplay_area_origin,
+                &self.gameplay_area_extension,
+            );
+        }
+
+        self.update_cells();
+    }
+
+    /// Returns true if a hazard is currently on the position.
+    pub fn occupies(&self, position: &Vector<u16>) -> bool {
+        return self.occupied.contains(position);
+    }
+
+    /// Returns true if a hazard can ever reach the position.
+    pub fn covers(&self, position: &Vector<u16>) -> bool {
+        return self.hazards.iter().any(|hazard| hazard.covers(position));
+    }
+
+    pub fn render(&mut self, cell_matrix: &mut CellMatrix) {
+        self.cell_group.render(cell_matrix);
+    }
+
+    fn update_cells(&mut self) {
+        for hazard in self.hazards.iter() {
+            for (position, cell) in
+                hazard.cells(&self.gameplay_area_origin, &self.gameplay_area_extension)
+            {
+                self.occupied.push(position.clone());
+                self.cell_group.set_cell(position, cell);
+            }
+        }
+    }
+}
+
+fn offset_position(
+    position: &Vector<u16>,
+    offset: (i32, i32),
+    gameplay_area_origin: &Vector<u16>,
+    gameplay_area_extension: &Vector<u16>,
+) -> Option<Vector<u16>> {
+    let x = position.x() as i32 + offset.0;
+    let y = position.y() as i32 + offset.1;
+
+    if x < gameplay_area_origin.x() as i32
+        || y < gameplay_area_origin.y() as i32
+        || x >= gameplay_area_extension.x() as i32
+        || y >= gameplay_area_extension.y() as i32
+    {
+        return None;
+    }
+
+    return Some(Vector::<u16>::new(x as u16, y as u16));
+}
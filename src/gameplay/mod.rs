@@ -1,3 +1,11 @@
+pub mod achievements;
+pub mod death_cause;
 pub mod fruit;
+pub mod game_mode;
+pub mod hazard;
+pub mod portal;
+pub mod replay;
+pub mod skin;
 pub mod snake;
+pub mod statistics;
 pub mod wall;
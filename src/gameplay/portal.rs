@@ -0,0 +1,134 @@
+use crate::{
+    cells::{
+        cell::{Cell, CellType},
+        cell_group::CellGroup,
+        cell_matrix::CellMatrix,
+        glyph::Glyph,
+        vector::Vector,
+    },
+    core::{random::Random, theme::Theme},
+};
+
+use super::hazard::Hazards;
+
+const PORTAL_PAIRS: usize = 2;
+const PORTAL_MARGIN: u16 = 2;
+
+pub struct Portals {
+    pairs: Vec<(Vector<u16>, Vector<u16>)>,
+    cell_group: CellGroup,
+}
+
+impl Portals {
+    pub fn new(
+        gameplay_area_origin: &Vector<u16>,
+        gameplay_area_extension: &Vector<u16>,
+        hazards: &Hazards,
+        reserved: &[Vector<u16>],
+        random: &mut Random,
+    ) -> Portals {
+        let mut placed: Vec<Vector<u16>> = Vec::new();
+
+        // Portals keep a margin from the walls so the exit cell is always inside the gameplay area
+        while placed.len() < PORTAL_PAIRS * 2 {
+            let x = random.random_range(
+                gameplay_area_origin.x() + PORTAL_MARGIN
+                    ..gameplay_area_extension.x() - PORTAL_MARGIN,
+            );
+            let y = random.random_range(
+                gameplay_area_origin.y() + PORTAL_MARGIN
+                    ..gameplay_area_extension.y() - PORTAL_MARGIN,
+            );
+
+            let possible_position = Vector::<u16>::new(x, y);
+            let is_far = |position: &Vector<u16>| {
+                position.x().abs_diff(x) > PORTAL_MARGIN || position.y().abs_diff(y) > PORTAL_MARGIN
+            };
+
+            if placed.iter().all(is_far)
+                && reserved.iter().all(is_far)
+                && neighbourhood(&possible_position)
+                    .iter()
+                    .all(|position| !hazards.covers(position))
+            {
+                placed.push(possible_position);
+            }
+        }
+
+        return Portals::restore(
+            placed
+                .chunks(2)
+                .map(|pair| (pair[0].clone(), pair[1].clone()))
+                .collect(),
+        );
+    }
+
+    pub fn restore(pairs: Vec<(Vector<u16>, Vector<u16>)>) -> Portals {
+        let mut portals = Portals {
+            pairs,
+            cell_group: CellGroup::new(),
+        };
+
+        let theme = Theme::current();
+
+        for (i, (entrance, exit)) in portals.pairs.iter().enumerate() {
+            for position in [entrance, exit] {
+                portals.cell_group.set_cell(
+                    position.clone(),
+                    Cell::new(
+                        Glyph::Portal.char(),
+                        theme.background(),
+                        theme.portals()[i % PORTAL_PAIRS],
+                        CellType::Portal,
+                    ),
+                );
+            }
+        }
+
+        return portals;
+    }
+
+    pub fn none() -> Portals {
+        return Portals {
+            pairs: Vec::new(),
+            cell_group: CellGroup::new(),
+        };
+    }
+
+    pub fn pairs(&self) -> &[(Vector<u16>, Vector<u16>)] {
+        return &self.pairs;
+    }
+
+    /// Returns the paired portal if there is a portal on the position.
+    pub fn destination(&self, position: &Vector<u16>) -> Option<Vector<u16>> {
+        for (entrance, exit) in &self.pairs {
+            if entrance == position {
+                return Some(exit.clone());
+            }
+
+            if exit == position {
+                return Some(entrance.clone());
+            }
+        }
+
+        return None;
+    }
+
+    pub fn covers(&self, position: &Vector<u16>) -> bool {
+        return self.destination(position).is_some();
+    }
+
+    pub fn render(&mut self, cell_matrix: &mut CellMatrix) {
+        self.cell_group.render(cell_matrix);
+    }
+}
+
+fn neighbourhood(position: &Vector<u16>) -> [Vector<u16>; 5] {
+    return [
+        position.clone(),
+        Vector::<u16>::new(position.x(), position.y() - 1),
+        Vector::<u16>::new(position.x(), position.y() + 1),
+        Vector::<u16>::new(position.x() - 1, position.y()),
+        Vector::<u16>::new(position.x() + 1, position.y()),
+    ];
+}
@@ -0,0 +1,38 @@
+use std::collections::VecDeque;
+
+use crate::cells::cell_matrix::CellMatrix;
+
+/// Ring buffer with the most recent frames of a game.
+pub struct ReplayBuffer {
+    frames: VecDeque<CellMatrix>,
+    capacity: usize,
+}
+
+impl ReplayBuffer {
+    pub fn new(capacity: usize) -> ReplayBuffer {
+        return ReplayBuffer {
+            frames: VecDeque::with_capacity(capacity),
+            capacity,
+        };
+    }
+
+    pub fn push(&mut self, frame: CellMatrix) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+
+        self.frames.push_back(frame);
+    }
+
+    pub fn len(&self) -> usize {
+        return self.frames.len();
+    }
+
+    pub fn frame(&self, index: usize) -> Option<&CellMatrix> {
+        return self.frames.get(index);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    cells::{glyph::Glyph, vector::Vector},
+    core::settings::Settings,
+};
+
+use super::{
+    achievements::{Achievement, Achievements, ACHIEVEMENTS},
+    snake::Direction,
+};
+
+// Shades of the blocks along the body, going back and forth every four segments
+const SHADE_PATTERNS: [[usize; 4]; 2] = [[0, 1, 2, 3], [3, 2, 1, 0]];
+const ARROW_HEADS: [char; 4] = ['▲', '▼', '◀', '▶'];
+const LINE_TAILS: [char; 4] = ['╵', '╷', '╴', '╶'];
+
+/// How the segments between the head and the tail are drawn.
+enum BodyStyle {
+    /// Block characters with shades that go back and forth along the body.
+    Shaded,
+    /// Box drawing lines that bend where the snake turned.
+    Pipes,
+}
+
+/// Look of the snake. Characters given per direction are in the order up, down, left and right.
+pub struct Skin {
+    id: &'static str,
+    name: &'static str,
+    /// Pointing where the snake is going. The shape coded head is used when there are none.
+    heads: Option<[char; 4]>,
+    body: BodyStyle,
+    /// Pointing towards the rest of the body. The tail is drawn like the body when there are none.
+    tails: Option<[char; 4]>,
+    /// Whether the body fades towards the background color the closer it is to the tail.
+    gradient: bool,
+    /// Id of the achievement that unlocks the skin, the skins without one are always available.
+    achievement: Option<&'static str>,
+}
+
+pub const SKINS: [Skin; 5] = [
+    Skin {
+        id: "classic",
+        name: "CLASSIC",
+        heads: None,
+        body: BodyStyle::Shaded,
+        tails: None,
+        gradient: false,
+        achievement: None,
+    },
+    Skin {
+        id: "arrowhead",
+        name: "ARROWHEAD",
+        heads: Some(ARROW_HEADS),
+        body: BodyStyle::Shaded,
+        tails: None,
+        gradient: false,
+        achievement: Some("first_bite"),
+    },
+    Skin {
+        id: "pipes",
+        name: "PIPES",
+        heads: Some(ARROW_HEADS),
+        body: BodyStyle::Pipes,
+        tails: Some(LINE_TAILS),
+        gradient: false,
+        achievement: Some("growing"),
+    },
+    Skin {
+        id: "comet",
+        name: "COMET",
+        heads: Some(ARROW_HEADS),
+        body: BodyStyle::Shaded,
+        tails: None,
+        gradient: true,
+        achievement: Some("regular"),
+    },
+    Skin {
+        id: "serpent",
+        name: "SERPENT",
+        heads: Some(ARROW_HEADS),
+        body: BodyStyle::Pipes,
+        tails: Some(LINE_TAILS),
+        gradient: true,
+        achievement: Some("long_snake"),
+    },
+];
+
+impl Skin {
+    /// The skin picked in the settings, or the classic one if it isn't unlocked anymore.
+    pub fn current(achievements: &Achievements) -> &'static Skin {
+        let id = Settings::load().skin();
+
+        return Skin::unlocked(achievements)
+            .into_iter()
+            .find(|skin| skin.id == id)
+            .unwrap_or(&SKINS[0]);
+    }
+
+    pub fn unlocked(achievements: &Achievements) -> Vec<&'static Skin> {
+        return SKINS
+            .iter()
+            .filter(|skin| match skin.achievement {
+                Some(id) => ACHIEVEMENTS.iter().any(|achievement| {
+                    achievement.id() == id && achievements.is_unlocked(achievement)
+                }),
+                None => true,
+            })
+            .collect();
+    }
+
+    /// Returns the skin that the achievement unlocks, if any.
+    pub fn unlocked_by(achievement: &Achievement) -> Option<&'static Skin> {
+        return SKINS
+            .iter()
+            .find(|skin| skin.achievement == Some(achievement.id()));
+    }
+
+    pub fn id(&self) -> &'static str {
+        return self.id;
+    }
+
+    pub fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    pub fn gradient(&self) -> bool {
+        return self.gradient;
+    }
+
+    /// Returns the character of a segment of the body, which goes from the head to the tail.
+    pub fn segment_char(&self, body: &[Vector<u16>], index: usize, direction: Direction) -> char {
+        if index == 0 {
+            return match self.heads {
+                Some(heads) => directional_char(heads, direction),
+                None => Glyph::SnakeHead.char(),
+            };
+        }
+
+        let towards_head = connection(&body[index], &body[index - 1]);
+
+        if index == body.len() - 1 {
+            if let (Some(tails), Some(towards_head)) = (self.tails, towards_head) {
+                return directional_char(tails, towards_head);
+            }
+        }
+
+        return match self.body {
+            BodyStyle::Shaded => {
+                Glyph::SnakeBody(SHADE_PATTERNS[(index / 4) % 2][index % 4]).char()
+            }
+            BodyStyle::Pipes => pipe_char(
+                towards_head,
+                body.get(index + 1)
+                    .and_then(|next| connection(&body[index], next)),
+            ),
+        };
+    }
+}
+
+fn directional_char(chars: [char; 4], direction: Direction) -> char {
+    return match direction {
+        Direction::Up => chars[0],
+        Direction::Down => chars[1],
+        Direction::Left => chars[2],
+        Direction::Right => chars[3],
+    };
+}
+
+/// Direction of the step between two segments, or none if they aren't next to each other because
+/// the snake went through a portal.
+fn connection(from: &Vector<u16>, to: &Vector<u16>) -> Option<Direction> {
+    let offset = (
+        to.x() as i32 - from.x() as i32,
+        to.y() as i32 - from.y() as i32,
+    );
+
+    return [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ]
+    .into_iter()
+    .find(|direction| direction.offset() == offset);
+}
+
+/// Line joining the two sides of a segment. A missing side continues the other one straight.
+fn pipe_char(first: Option<Direction>, second: Option<Direction>) -> char {
+    let (first, second) = match (first, second) {
+        (Some(first), Some(second)) => (first, second),
+        (Some(side), None) | (None, Some(side)) => (side, side.opposite()),
+        (None, None) => return '═',
+    };
+
+    return match (first, second) {
+        (Direction::Up, Direction::Down) | (Direction::Down, Direction::Up) => '║',
+        (Direction::Down, Direction::Right) | (Direction::Right, Direction::Down) => '╔',
+        (Direction::Down, Direction::Left) | (Direction::Left, Direction::Down) => '╗',
+        (Direction::Up, Direction::Right) | (Direction::Right, Direction::Up) => '╚',
+        (Direction::Up, Direction::Left) | (Direction::Left, Direction::Up) => '╝',
+        _ => '═',
+    };
+}
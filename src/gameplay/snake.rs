@@ -1,16 +1,23 @@
-use termion::event::Key;
+use termion::{color::Rgb, event::Key};
 
 use crate::{
     cells::{
-        cell::{Cell, CellType},
+        cell::{interpolated_color, Cell, CellType},
         cell_group::CellGroup,
         cell_matrix::CellMatrix,
-        color::Color,
         vector::Vector,
     },
-    INITIAL_SNAKE_LENGTH,
+    core::theme::Theme,
 };
 
+use super::{
+    portal::Portals,
+    skin::{Skin, SKINS},
+};
+
+// How close to the background color the tail gets with the skins that have a gradient
+const GRADIENT_FADE: f32 = 0.7;
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
     Up,
@@ -19,49 +26,167 @@ pub enum Direction {
     Right,
 }
 
+impl Direction {
+    pub fn from_name(name: &str) -> Option<Direction> {
+        return match name {
+            "up" => Some(Direction::Up),
+            "down" => Some(Direction::Down),
+            "left" => Some(Direction::Left),
+            "right" => Some(Direction::Right),
+            _ => None,
+        };
+    }
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+        };
+    }
+
+    pub fn opposite(&self) -> Direction {
+        return match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        };
+    }
+
+    pub fn offset(&self) -> (i32, i32) {
+        return match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        };
+    }
+}
+
 pub struct Snake {
     body: Vec<Vector<u16>>,
     direction: Direction,
+    /// Direction of the last step, which the snake can't turn back on.
+    facing: Direction,
     speed: f32,
+    aspect_ratio: f32,
     movement_accumulator: f32,
+    bg_color: Rgb,
+    head_color: Rgb,
+    body_color: Rgb,
+    skin: &'static Skin,
     cell_group: CellGroup,
 }
 
 impl Snake {
-    pub fn new(position: &Vector<u16>, speed: f32) -> Snake {
+    pub fn new(position: &Vector<u16>, speed: f32, length: u16) -> Snake {
         let mut body = Vec::new();
 
-        for i in 0..INITIAL_SNAKE_LENGTH {
+        for i in 0..length {
             body.push(Vector::<u16>::new(position.x(), position.y() + i));
         }
 
+        let theme = Theme::current();
+
         return Snake {
             body,
             direction: Direction::Up,
+            facing: Direction::Up,
             speed,
+            aspect_ratio: 1.0,
             movement_accumulator: 0.0,
+            bg_color: theme.background(),
+            head_color: theme.snake_head(),
+            body_color: theme.snake_body(),
+            skin: &SKINS[0],
+            cell_group: CellGroup::new(),
+        };
+    }
+
+    pub fn restore(
+        body: Vec<Vector<u16>>,
+        direction: Direction,
+        facing: Direction,
+        speed: f32,
+        movement_accumulator: f32,
+    ) -> Snake {
+        let theme = Theme::current();
+
+        return Snake {
+            body,
+            direction,
+            facing,
+            speed,
+            aspect_ratio: 1.0,
+            movement_accumulator,
+            bg_color: theme.background(),
+            head_color: theme.snake_head(),
+            body_color: theme.snake_body(),
+            skin: &SKINS[0],
             cell_group: CellGroup::new(),
         };
     }
 
     pub fn none() -> Snake {
+        let theme = Theme::current();
+
         return Snake {
             body: Vec::new(),
             direction: Direction::Up,
+            facing: Direction::Up,
             speed: 0.0,
+            aspect_ratio: 1.0,
             movement_accumulator: 0.0,
+            bg_color: theme.background(),
+            head_color: theme.snake_head(),
+            body_color: theme.snake_body(),
+            skin: &SKINS[0],
             cell_group: CellGroup::new(),
         };
     }
 
-    pub fn move_forward(&mut self, frame_duration: f64) -> Option<Vector<u16>> {
+    pub fn body(&self) -> &[Vector<u16>] {
+        return &self.body;
+    }
+
+    pub fn direction(&self) -> Direction {
+        return self.direction;
+    }
+
+    pub fn facing(&self) -> Direction {
+        return self.facing;
+    }
+
+    pub fn speed(&self) -> f32 {
+        return self.speed;
+    }
+
+    pub fn movement_accumulator(&self) -> f32 {
+        return self.movement_accumulator;
+    }
+
+    /// How many times taller than wide the cells look. Vertical steps take that many times longer,
+    /// so the speed looks the same in every direction.
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: f32) {
+        self.aspect_ratio = aspect_ratio;
+    }
+
+    pub fn move_forward(&mut self, frame_duration: f64, portals: &Portals) -> Option<Vector<u16>> {
         self.movement_accumulator += frame_duration as f32;
 
-        if self.movement_accumulator * self.speed >= 1.0 {
+        let speed = match self.direction {
+            Direction::Up | Direction::Down => self.speed / self.aspect_ratio,
+            Direction::Left | Direction::Right => self.speed,
+        };
+
+        if self.movement_accumulator * speed >= 1.0 {
             self.movement_accumulator = 0.0;
 
-            let new_head = self.new_head();
+            let new_head = self.new_head(portals);
 
+            self.facing = self.direction;
             self.body.insert(0, new_head.clone());
             self.cell_group
                 .set_cell(self.body.pop().unwrap(), Cell::new_empty());
@@ -72,82 +197,136 @@ impl Snake {
         return None;
     }
 
-    pub fn grow(&mut self) {
-        let new_head = self.new_head();
+    pub fn grow(&mut self, portals: &Portals) {
+        let new_head = self.new_head(portals);
 
+        self.facing = self.direction;
         self.body.insert(0, new_head);
     }
 
+    /// Turns are checked against the last step instead of the chosen direction, so two quick
+    /// turns before the next step can't reverse the snake into itself.
     pub fn update(&mut self, pressed_key: Option<Key>) {
-        match pressed_key {
-            Some(Key::Up) => {
-                if self.direction != Direction::Down {
-                    self.change_direction(Direction::Up);
-                }
-            }
-            Some(Key::Down) => {
-                if self.direction != Direction::Up {
-                    self.change_direction(Direction::Down);
-                }
-            }
-            Some(Key::Left) => {
-                if self.direction != Direction::Right {
-                    self.change_direction(Direction::Left);
-                }
-            }
-            Some(Key::Right) => {
-                if self.direction != Direction::Left {
-                    self.change_direction(Direction::Right);
-                }
-            }
-            _ => (),
+        let direction = match pressed_key {
+            Some(Key::Up) => Direction::Up,
+            Some(Key::Down) => Direction::Down,
+            Some(Key::Left) => Direction::Left,
+            Some(Key::Right) => Direction::Right,
+            _ => return,
+        };
+
+        if direction != self.facing.opposite() {
+            self.change_direction(direction);
         }
     }
 
+    pub fn set_skin(&mut self, skin: &'static Skin) {
+        self.skin = skin;
+    }
+
+    /// Paints the whole snake in one color.
+    pub fn set_color(&mut self, color: Rgb) {
+        self.head_color = color;
+        self.body_color = color;
+    }
+
+    /// Removes the last segment. Returns false if there was nothing left to remove.
+    pub fn dissolve_tail(&mut self) -> bool {
+        return match self.body.pop() {
+            Some(tail) => {
+                self.cell_group.set_cell(tail, Cell::new_empty());
+                true
+            }
+            None => false,
+        };
+    }
+
     pub fn change_direction(&mut self, direction: Direction) {
         self.direction = direction;
     }
 
     pub fn render(&mut self, cell_matrix: &mut CellMatrix) {
-        const PATTERNS: [[char; 4]; 2] = [['█', '▓', '▒', '░'], ['░', '▒', '▓', '█']];
-
-        let mut reverse_pattern = false;
         let body_len = self.body.len();
 
         for i in 0..body_len {
-            if i % 4 == 0 && i > 0 {
-                reverse_pattern = !reverse_pattern;
-            }
+            let char = self.skin.segment_char(&self.body, i, self.direction);
+            let color = if i == 0 {
+                self.head_color
+            } else if self.skin.gradient() {
+                interpolated_color(
+                    self.body_color,
+                    self.bg_color,
+                    GRADIENT_FADE * i as f32 / body_len as f32,
+                )
+            } else {
+                self.body_color
+            };
 
-            let pattern_idx = reverse_pattern as usize;
-            let char = PATTERNS[pattern_idx][i % 4];
-
-            self.set_cell(self.body[i].clone(), char);
+            self.set_cell(self.body[i].clone(), char, color);
         }
 
         self.cell_group.render(cell_matrix);
     }
 
-    fn new_head(&self) -> Vector<u16> {
-        let head = self.body[0].clone();
+    /// Entering a portal moves the head to the cell right after the paired portal, keeping the direction.
+    fn new_head(&self, portals: &Portals) -> Vector<u16> {
+        let head = self.step(&self.body[0]);
 
-        return match self.direction {
-            Direction::Up => Vector::<u16>::new(head.x(), head.y() - 1),
-            Direction::Down => Vector::<u16>::new(head.x(), head.y() + 1),
-            Direction::Left => Vector::<u16>::new(head.x() - 1, head.y()),
-            Direction::Right => Vector::<u16>::new(head.x() + 1, head.y()),
+        return match portals.destination(&head) {
+            Some(exit) => self.step(&exit),
+            None => head,
         };
     }
 
-    fn set_cell(&mut self, position: Vector<u16>, char: char) {
+    fn step(&self, position: &Vector<u16>) -> Vector<u16> {
+        let (x_offset, y_offset) = self.direction.offset();
+
+        return Vector::<u16>::new(
+            (position.x() as i32 + x_offset) as u16,
+            (position.y() as i32 + y_offset) as u16,
+        );
+    }
+
+    fn set_cell(&mut self, position: Vector<u16>, char: char, color: Rgb) {
         self.cell_group.set_cell(
             position,
-            Cell::new(
-                char,
-                Color::Black.to_rgb(),
-                Color::Green.to_rgb(),
-                CellType::Snake,
-            ),
+            Cell::new(char, self.bg_color, color, CellType::Snake),
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use termion::event::Key;
+
+    use super::{Direction, Snake};
+    use crate::{cells::vector::Vector, gameplay::portal::Portals};
+
+    #[test]
+    fn turns_are_checked_against_the_last_step() {
+        let mut snake = Snake::new(&Vector::<u16>::new(5, 5), 1.0, 3);
+
+        // Two turns before the snake moves, like during the countdown
+        snake.update(Some(Key::Left));
+        snake.update(Some(Key::Down));
+
+        assert!(snake.direction() == Direction::Left);
+
+        snake.move_forward(1.0, &Portals::none());
+        snake.update(Some(Key::Down));
+
+        assert!(snake.direction() == Direction::Down);
+        assert_eq!(snake.body()[0], Vector::<u16>::new(4, 5));
+    }
+
+    #[test]
+    fn the_snake_can_turn_back_to_where_it_was_facing() {
+        let mut snake = Snake::new(&Vector::<u16>::new(5, 5), 1.0, 3);
+
+        snake.update(Some(Key::Right));
+        snake.update(Some(Key::Up));
+        snake.update(Some(Key::Down));
+
+        assert!(snake.direction() == Direction::Up);
+    }
+}
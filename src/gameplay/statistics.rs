@@ -0,0 +1,81 @@
+use crate::core::storage::Record;
+
+use super::death_cause::DeathCause;
+
+const STATISTICS_FILE: &str = "statistics";
+
+/// Lifetime statistics, kept across sessions.
+pub struct Statistics {
+    games_played: u32,
+    total_fruit: u32,
+    longest_snake: u32,
+    total_play_time: f64,
+    deaths: [u32; DeathCause::ALL.len()],
+}
+
+impl Statistics {
+    pub fn load() -> Statistics {
+        let record = Record::load(STATISTICS_FILE).unwrap_or(Record::new());
+        let mut deaths = [0; DeathCause::ALL.len()];
+
+        for (i, cause) in DeathCause::ALL.iter().enumerate() {
+            deaths[i] = record
+                .get_parsed(&format!("deaths_{}", cause.name()))
+                .unwrap_or(0);
+        }
+
+        return Statistics {
+            games_played: record.get_parsed("games_played").unwrap_or(0),
+            total_fruit: record.get_parsed("total_fruit").unwrap_or(0),
+            longest_snake: record.get_parsed("longest_snake").unwrap_or(0),
+            total_play_time: record.get_parsed("total_play_time").unwrap_or(0.0),
+            deaths,
+        };
+    }
+
+    pub fn save(&self) {
+        let mut record = Record::new();
+
+        record.set("games_played", self.games_played.to_string());
+        record.set("total_fruit", self.total_fruit.to_string());
+        record.set("longest_snake", self.longest_snake.to_string());
+        record.set("total_play_time", self.total_play_time.to_string());
+
+        for (i, cause) in DeathCause::ALL.iter().enumerate() {
+            record.set(
+                &format!("deaths_{}", cause.name()),
+                self.deaths[i].to_string(),
+            );
+        }
+
+        let _ = record.save(STATISTICS_FILE);
+    }
+
+    pub fn games_played(&self) -> u32 {
+        return self.games_played;
+    }
+
+    pub fn total_fruit(&self) -> u32 {
+        return self.total_fruit;
+    }
+
+    pub fn longest_snake(&self) -> u32 {
+        return self.longest_snake;
+    }
+
+    pub fn total_play_time(&self) -> f64 {
+        return self.total_play_time;
+    }
+
+    pub fn deaths(&self, cause: DeathCause) -> u32 {
+        return self.deaths[cause as usize];
+    }
+
+    pub fn record_game(&mut self, fruit: u32, length: u32, play_time: f64, cause: DeathCause) {
+        self.games_played += 1;
+        self.total_fruit += fruit;
+        self.longest_snake = self.longest_snake.max(length);
+        self.total_play_time += play_time;
+        self.deaths[cause as usize] += 1;
+    }
+}
@@ -1,7 +1,9 @@
-use crate::cells::cell::Cell;
+use crate::cells::cell::{Cell, CellType};
 use crate::cells::cell_group::CellGroup;
 use crate::cells::cell_matrix::CellMatrix;
+use crate::cells::glyph::Glyph;
 use crate::cells::vector::Vector;
+use crate::core::theme::Theme;
 
 pub struct Wall {
     cell_group: CellGroup,
@@ -9,24 +11,45 @@ pub struct Wall {
 
 impl Wall {
     pub fn new(width: u16, height: u16) -> Wall {
+        let theme = Theme::current();
+        let wall_cell = |glyph: Glyph| {
+            Cell::new(
+                glyph.char(),
+                theme.background(),
+                theme.wall(),
+                CellType::Solid,
+            )
+        };
         let mut cell_group = CellGroup::new();
 
-        cell_group.set_cell(Vector::<u16>::zero(), Cell::new_colorless('┏'));
-        cell_group.set_cell(Vector::<u16>::new(width - 1, 0), Cell::new_colorless('┓'));
-        cell_group.set_cell(Vector::<u16>::new(0, height - 1), Cell::new_colorless('┗'));
+        cell_group.set_cell(Vector::<u16>::zero(), wall_cell(Glyph::WallTopLeft));
+        cell_group.set_cell(
+            Vector::<u16>::new(width - 1, 0),
+            wall_cell(Glyph::WallTopRight),
+        );
+        cell_group.set_cell(
+            Vector::<u16>::new(0, height - 1),
+            wall_cell(Glyph::WallBottomLeft),
+        );
         cell_group.set_cell(
             Vector::<u16>::new(width - 1, height - 1),
-            Cell::new_colorless('┛'),
+            wall_cell(Glyph::WallBottomRight),
         );
 
         for x in 1..width - 1 {
-            cell_group.set_cell(Vector::<u16>::new(x, 0), Cell::new_colorless('━'));
-            cell_group.set_cell(Vector::<u16>::new(x, height - 1), Cell::new_colorless('━'));
+            cell_group.set_cell(Vector::<u16>::new(x, 0), wall_cell(Glyph::WallHorizontal));
+            cell_group.set_cell(
+                Vector::<u16>::new(x, height - 1),
+                wall_cell(Glyph::WallHorizontal),
+            );
         }
 
         for y in 1..height - 1 {
-            cell_group.set_cell(Vector::<u16>::new(0, y), Cell::new_colorless('┃'));
-            cell_group.set_cell(Vector::<u16>::new(width - 1, y), Cell::new_colorless('┃'));
+            cell_group.set_cell(Vector::<u16>::new(0, y), wall_cell(Glyph::WallVertical));
+            cell_group.set_cell(
+                Vector::<u16>::new(width - 1, y),
+                wall_cell(Glyph::WallVertical),
+            );
         }
 
         return Wall { cell_group };
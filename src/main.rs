@@ -1,37 +1,113 @@
+#![allow(clippy::needless_return, clippy::too_many_arguments)]
+
 mod cells;
 mod core;
 mod gameplay;
 mod scenes;
 mod ui;
 
-use core::{chronometer::Chronometer, scene_manager::SceneManager, terminal::Terminal};
+use std::{env, process};
+
+use cells::{color::ColorMode, glyph::GlyphMode};
+use core::{
+    chronometer::Chronometer, events::Event, input::Input, scene_manager::SceneManager,
+    scene_registry::SceneRegistry, terminal::Terminal,
+};
 
 use scenes::{
     gameplay_scene::build_gameplay_scene,
-    ui_scene::{build_game_over_scene, build_main_menu_scene, build_paused_scene},
+    scene::SceneId,
+    ui_scene::{
+        build_game_over_scene, build_main_menu_scene, build_paused_scene, build_settings_scene,
+        build_statistics_scene,
+    },
 };
+use ui::font::Font;
 
 const MINIMUM_WIDTH: u16 = 80;
 const MINIMUM_HEIGHT: u16 = 45;
 const FPS: u32 = 256;
 const SNAKE_SPEED: f32 = 10.0;
 const INITIAL_SNAKE_LENGTH: u16 = 2;
+const TRANSITION_DURATION: f64 = 0.35;
+const DEATH_REPLAY: bool = true;
+const MOUSE_STEERING: bool = false;
+const SHAPE_CODING: bool = false;
+const HALF_BLOCKS: bool = false;
+const EVEN_SPEED: bool = false;
+// Seconds counted down before the snake starts moving
+const COUNTDOWN: u16 = 3;
+// Terminal characters are about twice as tall as they are wide
+const CELL_ASPECT_RATIO: f32 = 2.0;
+const THEME: &str = "classic";
+const SKIN: &str = "classic";
+const BANNER_FONT: &str = "ansi-shadow";
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 fn main() {
-    let mut scene_manager = SceneManager::new();
+    // The detected color support can be overridden with --colors=truecolor|256|16|none
+    if let Some(name) =
+        env::args().find_map(|argument| argument.strip_prefix("--colors=").map(str::to_string))
+    {
+        match ColorMode::from_name(&name) {
+            Some(color_mode) => ColorMode::set_current(color_mode),
+            None => {
+                eprintln!(
+                    "Unknown color mode \"{}\", expected truecolor, 256, 16 or none",
+                    name
+                );
+                process::exit(2);
+            }
+        }
+    }
+
+    // The characters picked from the locale can be overridden with --glyphs=unicode|ascii
+    if let Some(name) =
+        env::args().find_map(|argument| argument.strip_prefix("--glyphs=").map(str::to_string))
+    {
+        match GlyphMode::from_name(&name) {
+            Some(glyph_mode) => GlyphMode::set_current(glyph_mode),
+            None => {
+                eprintln!("Unknown glyph set \"{}\", expected unicode or ascii", name);
+                process::exit(2);
+            }
+        }
+    }
+
+    // The font of the titles can be picked with --font=<name>, from the bundled fonts or the
+    // .flf files in the fonts directory of the data directory
+    if let Some(name) =
+        env::args().find_map(|argument| argument.strip_prefix("--font=").map(str::to_string))
+    {
+        Font::set_banner(&name);
+    }
+
     let mut chronometer = Chronometer::new(FPS);
     let mut terminal = Terminal::new();
+    let mut scene_registry = SceneRegistry::new();
 
-    let width = terminal.width();
-    let height = terminal.height();
+    scene_registry.register(SceneId::MainMenu, |width, height| {
+        Box::new(build_main_menu_scene(width, height))
+    });
+    scene_registry.register(SceneId::Gameplay, |width, height| {
+        Box::new(build_gameplay_scene(width, height))
+    });
+    scene_registry.register(SceneId::Paused, |width, height| {
+        Box::new(build_paused_scene(width, height))
+    });
+    scene_registry.register(SceneId::GameOver, |width, height| {
+        Box::new(build_game_over_scene(width, height))
+    });
+    scene_registry.register(SceneId::Statistics, |width, height| {
+        Box::new(build_statistics_scene(width, height))
+    });
+    scene_registry.register(SceneId::Settings, |width, height| {
+        Box::new(build_settings_scene(width, height))
+    });
 
-    scene_manager.add_scene(Box::new(build_main_menu_scene(width, height)));
-    scene_manager.add_scene(Box::new(build_gameplay_scene(width, height)));
-    scene_manager.add_scene(Box::new(build_paused_scene(width, height)));
-    scene_manager.add_scene(Box::new(build_game_over_scene(width, height)));
+    let mut scene_manager = SceneManager::new(scene_registry, terminal.width(), terminal.height());
 
-    scene_manager.set_current_scene("main_menu");
+    scene_manager.handle_update_result(Event::GoToMenu);
 
     terminal.hide_cursor();
 
@@ -40,16 +116,31 @@ fn main() {
             continue;
         }
 
-        let current_scene = scene_manager.current_scene_mut().as_mut().unwrap();
+        if scene_manager.update_transition(chronometer.real_frame_duration(), &mut terminal) {
+            terminal.flush();
+            continue;
+        }
+
+        let current_scene = scene_manager.current_scene_mut().unwrap();
 
         current_scene.write(&mut terminal);
         terminal.flush();
 
-        let event = current_scene.update(
-            terminal.get_pressed_key(),
-            chronometer.current_fps(),
-            chronometer.real_frame_duration(),
-        );
+        let (pressed_key, mouse_event) = match terminal.get_input() {
+            Some(Input::Key(key)) => (Some(key), Event::None),
+            Some(Input::Mouse(mouse_input)) => (None, current_scene.handle_mouse(mouse_input)),
+            None => (None, Event::None),
+        };
+
+        let event = if mouse_event != Event::None {
+            mouse_event
+        } else {
+            current_scene.update(
+                pressed_key,
+                chronometer.current_fps(),
+                chronometer.real_frame_duration(),
+            )
+        };
 
         scene_manager.handle_update_result(event);
 
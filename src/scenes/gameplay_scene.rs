@@ -1,19 +1,58 @@
-use std::{any::Any, collections::HashMap};
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+};
 
 use termion::event::Key;
 
 use crate::{
-    cells::{cell::CellType, cell_matrix::CellMatrix, color::Color, vector::Vector},
-    core::{events::Event, terminal::Terminal},
-    gameplay::{fruit::Fruit, snake::Snake, wall::Wall},
-    ui::{text::Text, ui_element::Orientation},
-    SNAKE_SPEED,
+    cells::{
+        cell::{Cell, CellType},
+        cell_matrix::{CellMatrix, Presentation},
+        glyph::{Glyph, GlyphMode},
+        vector::Vector,
+    },
+    core::{
+        events::Event, input::MouseInput, random::Random, settings::Settings, storage::Record,
+        terminal::Terminal, theme::Theme,
+    },
+    gameplay::{
+        achievements::Achievements,
+        death_cause::DeathCause,
+        fruit::Fruit,
+        game_mode::GameMode,
+        hazard::Hazards,
+        portal::Portals,
+        replay::ReplayBuffer,
+        skin::Skin,
+        snake::{Direction, Snake},
+        statistics::Statistics,
+        wall::Wall,
+    },
+    ui::{font::Font, text::Text, ui_element::Orientation},
+    CELL_ASPECT_RATIO,
 };
 
 use super::scene::Scene;
 
+const SAVE_FILE: &str = "save";
+const TOAST_DURATION: f64 = 3.0;
+const DEATH_FLASH_DURATION: f64 = 0.6;
+const DEATH_FLASH_INTERVAL: f64 = 0.1;
+const DEATH_DISSOLVE_DURATION: f64 = 0.8;
+const REPLAY_SECONDS: f32 = 3.0;
+const REPLAY_SLOWDOWN: f64 = 2.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Everything stands still until the countdown is over, but the direction can be picked.
+    CountingDown,
+    Playing,
+    Dying,
+    Replaying,
+}
+
 pub struct GameplayScene {
-    name: String,
     cell_matrix: CellMatrix,
     texts: HashMap<String, Text>,
     gameplay_area_origin: Vector<u16>,
@@ -21,26 +60,32 @@ pub struct GameplayScene {
     wall: Wall,
     snake: Snake,
     fruit: Fruit,
+    hazards: Hazards,
+    portals: Portals,
+    mode: GameMode,
+    random: Random,
     score: u32,
+    play_time: f64,
+    in_progress: bool,
+    death_cause: Option<DeathCause>,
+    statistics: Statistics,
+    achievements: Achievements,
+    toasts: VecDeque<String>,
+    toast_time: f64,
+    phase: Phase,
+    phase_time: f64,
+    collision: Vector<u16>,
+    pending_end: Event,
+    replay: ReplayBuffer,
+    /// Drawn over the arena as it is written, so it never becomes part of the arena cells.
+    countdown: Text,
+    /// Loaded when the scene is entered, as they can only change in the settings scene.
+    settings: Settings,
 }
 
 impl Scene for GameplayScene {
-    fn new(name: String, width: u16, height: u16) -> Self {
-        return GameplayScene {
-            name,
-            cell_matrix: CellMatrix::new(width, height),
-            texts: HashMap::new(),
-            gameplay_area_origin: Vector::<u16>::new(1, 1),
-            gameplay_area_extension: Vector::<u16>::new(width - 1, height - 2),
-            wall: Wall::new(width, height - 1),
-            snake: Snake::none(),
-            fruit: Fruit::none(),
-            score: 0,
-        };
-    }
-
-    fn name(&self) -> String {
-        return self.name.clone();
+    fn new(width: u16, height: u16) -> Self {
+        return GameplayScene::with_presentation(width, height, preferred_presentation());
     }
 
     fn add_text(&mut self, text: Text) {
@@ -54,8 +99,72 @@ impl Scene for GameplayScene {
             .set_string(new_text_string);
     }
 
+    fn enter(&mut self, event: &Event) -> Event {
+        // New games use the presentation from the settings and saved games the one they were
+        // saved with, which needs the scene to be built again when it changed
+        let presentation = match *event {
+            Event::Start { .. } => preferred_presentation(),
+            Event::Continue => saved_presentation().unwrap_or(self.cell_matrix.presentation()),
+            _ => self.cell_matrix.presentation(),
+        };
+
+        if presentation != self.cell_matrix.presentation() {
+            *self = build_gameplay_scene_with_presentation(
+                self.cell_matrix.width(),
+                self.cell_matrix.terminal_height(),
+                presentation,
+            );
+        }
+
+        self.settings = Settings::load();
+
+        match *event {
+            Event::Start { mode } => {
+                self.mode = mode;
+                self.start_new_game();
+                self.start_countdown();
+            }
+            Event::Restart => {
+                self.start_new_game();
+                self.start_countdown();
+            }
+            Event::Continue => {
+                if !self.load() {
+                    return Event::GoToMenu;
+                }
+
+                self.start_countdown();
+            }
+            Event::Resume => {
+                self.start_countdown();
+            }
+            Event::Abandon => {
+                return self.end_game(DeathCause::Quit);
+            }
+            Event::SaveAndQuit => {
+                return match self.save() {
+                    Ok(()) => Event::GoToMenu,
+                    Err(_) => Event::SaveFailed,
+                };
+            }
+            _ => {}
+        }
+
+        return Event::None;
+    }
+
     fn update(&mut self, pressed_key: Option<Key>, current_fps: f64, frame_duration: f64) -> Event {
         self.update_fps_text(current_fps);
+
+        match self.phase {
+            Phase::Dying => return self.update_death_animation(pressed_key, frame_duration),
+            Phase::Replaying => return self.update_replay(pressed_key, frame_duration),
+            Phase::CountingDown | Phase::Playing => {}
+        }
+
+        self.update_toast(frame_duration);
+        self.hazards.render(&mut self.cell_matrix);
+        self.portals.render(&mut self.cell_matrix);
         self.snake.render(&mut self.cell_matrix);
         self.fruit.render(&mut self.cell_matrix);
 
@@ -63,17 +172,73 @@ impl Scene for GameplayScene {
             return Event::Pause;
         }
 
+        if self.phase == Phase::CountingDown {
+            self.update_countdown(pressed_key, frame_duration);
+
+            return Event::None;
+        }
+
+        self.play_time += frame_duration;
         self.snake.update(pressed_key);
 
-        let head = self.snake.move_forward(frame_duration);
+        let head = self.snake.move_forward(frame_duration, &self.portals);
+
+        if head.is_some() {
+            self.replay.push(self.cell_matrix.snapshot());
+        }
 
         return self.handle_snake_update(head);
     }
 
+    fn handle_mouse(&mut self, mouse_input: MouseInput) -> Event {
+        let MouseInput::Click(position) = mouse_input else {
+            return Event::None;
+        };
+
+        if !matches!(self.phase, Phase::CountingDown | Phase::Playing)
+            || !self.settings.mouse_steering()
+        {
+            return Event::None;
+        }
+
+        let position = self.cell_matrix.cell_position(&position);
+
+        // The snake turns towards the side of its head that was clicked
+        let head = &self.snake.body()[0];
+        let key = match self.snake.direction() {
+            Direction::Up | Direction::Down if position.x() < head.x() => Key::Left,
+            Direction::Up | Direction::Down if position.x() > head.x() => Key::Right,
+            Direction::Left | Direction::Right if position.y() < head.y() => Key::Up,
+            Direction::Left | Direction::Right if position.y() > head.y() => Key::Down,
+            _ => return Event::None,
+        };
+
+        self.snake.update(Some(key));
+
+        return Event::None;
+    }
+
     fn write(&mut self, terminal: &mut Terminal) {
         self.cell_matrix.write(terminal);
+
+        if self.phase == Phase::CountingDown {
+            let mut frame = self.cell_matrix.presented();
+
+            self.countdown.render(&mut frame);
+            frame.write(terminal);
+        }
+    }
+
+    fn cell_matrix(&self) -> &CellMatrix {
+        return &self.cell_matrix;
     }
 
+    fn is_overlay(&self) -> bool {
+        return false;
+    }
+
+    fn set_backdrop(&mut self, _: CellMatrix) {}
+
     fn render_texts(&mut self) {
         for (_, text) in self.texts.iter_mut() {
             text.render(&mut self.cell_matrix);
@@ -85,33 +250,320 @@ impl Scene for GameplayScene {
         self.wall.render(&mut self.cell_matrix);
         self.render_texts();
     }
-
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        return self;
-    }
 }
 
 impl GameplayScene {
-    pub fn score(&self) -> u32 {
-        return self.score;
+    /// The height is in terminal rows. With half blocks the arena has twice as many rows of
+    /// cells, and the row under the bottom wall is left empty so the HUD doesn't share its
+    /// characters with the wall.
+    fn with_presentation(width: u16, height: u16, presentation: Presentation) -> GameplayScene {
+        let cell_matrix = match presentation {
+            Presentation::Characters => CellMatrix::new(width, height),
+            Presentation::HalfBlocks => CellMatrix::new_half_blocks(width, height),
+        };
+        let hud_height = match presentation {
+            Presentation::Characters => 1,
+            Presentation::HalfBlocks => 2,
+        };
+        let height = cell_matrix.height();
+        let theme = Theme::current();
+
+        // Placed above the snake in terminal rows, as it is drawn over the presented arena
+        let countdown = Text::new_big(
+            "countdown".to_string(),
+            Vector::<i32>::new(0, -(cell_matrix.terminal_height() as i32) / 4),
+            Orientation::Center,
+            Orientation::Center,
+            String::new(),
+            Font::banner(),
+            width,
+            cell_matrix.terminal_height(),
+            theme.background(),
+            theme.hud(),
+        );
+
+        return GameplayScene {
+            cell_matrix,
+            texts: HashMap::new(),
+            gameplay_area_origin: Vector::<u16>::new(1, 1),
+            gameplay_area_extension: Vector::<u16>::new(width - 1, height - hud_height - 1),
+            wall: Wall::new(width, height - hud_height),
+            snake: Snake::none(),
+            fruit: Fruit::none(),
+            hazards: Hazards::none(),
+            portals: Portals::none(),
+            mode: GameMode::Classic,
+            random: Random::new(),
+            score: 0,
+            play_time: 0.0,
+            in_progress: false,
+            death_cause: None,
+            statistics: Statistics::load(),
+            achievements: Achievements::load(),
+            toasts: VecDeque::new(),
+            toast_time: 0.0,
+            phase: Phase::Playing,
+            phase_time: 0.0,
+            collision: Vector::<u16>::zero(),
+            pending_end: Event::None,
+            replay: ReplayBuffer::new(0),
+            countdown,
+            settings: Settings::load(),
+        };
     }
 
-    pub fn start_new_game(&mut self) {
+    fn start_new_game(&mut self) {
+        self.finish_game();
+        self.random = match self.settings.seed() {
+            Some(seed) => Random::from_state(seed),
+            None => Random::new(),
+        };
         self.snake = Snake::new(
             &Vector::<u16>::new(self.cell_matrix.width() / 2, self.cell_matrix.height() / 2),
-            SNAKE_SPEED,
+            self.settings.snake_speed(),
+            self.settings.starting_length(),
         );
+        self.snake.set_aspect_ratio(self.step_aspect_ratio());
+        self.snake.set_skin(Skin::current(&self.achievements));
+
+        self.hazards = match self.mode {
+            GameMode::Hazards => {
+                Hazards::new(&self.gameplay_area_origin, &self.gameplay_area_extension)
+            }
+            _ => Hazards::none(),
+        };
+
+        self.portals = match self.mode {
+            GameMode::Portals => Portals::new(
+                &self.gameplay_area_origin,
+                &self.gameplay_area_extension,
+                &self.hazards,
+                self.snake.body(),
+                &mut self.random,
+            ),
+            _ => Portals::none(),
+        };
 
         self.fruit = Fruit::new(
             &self.cell_matrix,
             &self.gameplay_area_origin,
             &self.gameplay_area_extension,
+            &self.hazards,
+            &self.portals,
+            &mut self.random,
         );
 
         self.score = 0;
+        self.play_time = 0.0;
+        self.in_progress = true;
+        self.death_cause = None;
+        self.reset_phase();
         self.update_score_text(self.score);
     }
 
+    /// Records the current game in the lifetime statistics. Games that were not lost count as quit.
+    fn finish_game(&mut self) {
+        if !self.in_progress {
+            return;
+        }
+
+        self.in_progress = false;
+        self.statistics.record_game(
+            self.score,
+            self.snake.body().len() as u32,
+            self.play_time,
+            self.death_cause.unwrap_or(DeathCause::Quit),
+        );
+        self.statistics.save();
+        self.achievements.check(
+            &self.statistics,
+            0,
+            self.snake.body().len() as u32,
+            self.fill_ratio(),
+            self.play_time,
+        );
+    }
+
+    pub fn has_save() -> bool {
+        return Record::exists(SAVE_FILE);
+    }
+
+    /// Saves the current game, which is then no longer in progress until loaded back. The game
+    /// stays in progress if it couldn't be saved.
+    fn save(&mut self) -> io::Result<()> {
+        self.saved_record().save(SAVE_FILE)?;
+        self.in_progress = false;
+
+        return Ok(());
+    }
+
+    /// The state of the current game as it is saved.
+    fn saved_record(&self) -> Record {
+        let mut record = Record::new();
+
+        record.set_positions(
+            "gameplay_area",
+            &[
+                self.gameplay_area_origin.clone(),
+                self.gameplay_area_extension.clone(),
+            ],
+        );
+
+        record.set("mode", self.mode.name().to_string());
+        record.set("score", self.score.to_string());
+        record.set("play_time", self.play_time.to_string());
+        record.set_positions("snake_body", self.snake.body());
+        record.set("snake_direction", self.snake.direction().name().to_string());
+        record.set("snake_facing", self.snake.facing().name().to_string());
+        record.set("snake_speed", self.snake.speed().to_string());
+        record.set(
+            "movement_accumulator",
+            self.snake.movement_accumulator().to_string(),
+        );
+        record.set_positions("fruit", self.fruit.position().as_slice());
+        record.set("hazard_ticks", self.hazards.ticks().to_string());
+        record.set_positions(
+            "portals",
+            &self
+                .portals
+                .pairs()
+                .iter()
+                .flat_map(|(entrance, exit)| [entrance.clone(), exit.clone()])
+                .collect::<Vec<Vector<u16>>>(),
+        );
+        record.set("random_state", self.random.state().to_string());
+        record.set(
+            "half_blocks",
+            (self.cell_matrix.presentation() == Presentation::HalfBlocks).to_string(),
+        );
+
+        return record;
+    }
+
+    /// Restores the saved game and deletes the save. Returns false if there is no valid save. Games
+    /// saved with an arena of another size are kept for when the terminal has that size again.
+    fn load(&mut self) -> bool {
+        let Some(record) = Record::load(SAVE_FILE) else {
+            return false;
+        };
+
+        let gameplay_area = [
+            self.gameplay_area_origin.clone(),
+            self.gameplay_area_extension.clone(),
+        ];
+
+        if record.get_positions("gameplay_area").as_deref() != Some(gameplay_area.as_slice()) {
+            return false;
+        }
+
+        self.finish_game();
+        Record::remove(SAVE_FILE);
+
+        return self.restore(&record);
+    }
+
+    /// Restores a saved game. Returns false if the record isn't a valid save.
+    fn restore(&mut self, record: &Record) -> bool {
+        let (
+            Some(mode),
+            Some(score),
+            Some(snake_body),
+            Some(snake_direction),
+            Some(snake_speed),
+            Some(movement_accumulator),
+            Some(fruit),
+            Some(hazard_ticks),
+            Some(portals),
+            Some(random_state),
+        ) = (
+            record.get("mode").and_then(GameMode::from_name),
+            record.get_parsed::<u32>("score"),
+            record.get_positions("snake_body"),
+            record.get("snake_direction").and_then(Direction::from_name),
+            record.get_parsed::<f32>("snake_speed"),
+            record.get_parsed::<f32>("movement_accumulator"),
+            record.get_positions("fruit"),
+            record.get_parsed::<u32>("hazard_ticks"),
+            record.get_positions("portals"),
+            record.get_parsed::<u64>("random_state"),
+        )
+        else {
+            return false;
+        };
+
+        if snake_body.is_empty()
+            || !snake_body
+                .iter()
+                .chain(&fruit)
+                .chain(&portals)
+                .all(|position| self.is_in_gameplay_area(position))
+        {
+            return false;
+        }
+
+        self.mode = mode;
+        self.score = score;
+        // Saves from before the snake kept its last step were saved right after it
+        let snake_facing = record
+            .get("snake_facing")
+            .and_then(Direction::from_name)
+            .unwrap_or(snake_direction);
+
+        self.snake = Snake::restore(
+            snake_body,
+            snake_direction,
+            snake_facing,
+            snake_speed,
+            movement_accumulator,
+        );
+        self.snake.set_aspect_ratio(self.step_aspect_ratio());
+        self.snake.set_skin(Skin::current(&self.achievements));
+        self.fruit = match fruit.first() {
+            Some(position) => Fruit::at(position.clone()),
+            None => Fruit::none(),
+        };
+        self.hazards = match self.mode {
+            GameMode::Hazards => Hazards::restore(
+                &self.gameplay_area_origin,
+                &self.gameplay_area_extension,
+                hazard_ticks,
+            ),
+            _ => Hazards::none(),
+        };
+        self.portals = Portals::restore(
+            portals
+                .chunks_exact(2)
+                .map(|pair| (pair[0].clone(), pair[1].clone()))
+                .collect(),
+        );
+        self.random = Random::from_state(random_state);
+        self.play_time = record.get_parsed("play_time").unwrap_or(0.0);
+        self.in_progress = true;
+        self.death_cause = None;
+        self.reset_phase();
+        self.update_score_text(self.score);
+
+        return true;
+    }
+
+    fn is_in_gameplay_area(&self, position: &Vector<u16>) -> bool {
+        return (self.gameplay_area_origin.x()..self.gameplay_area_extension.x())
+            .contains(&position.x())
+            && (self.gameplay_area_origin.y()..self.gameplay_area_extension.y())
+                .contains(&position.y());
+    }
+
+    /// Half blocks already make the cells square, so only characters need their vertical steps
+    /// slowed down.
+    fn step_aspect_ratio(&self) -> f32 {
+        if self.cell_matrix.presentation() == Presentation::Characters && self.settings.even_speed()
+        {
+            return CELL_ASPECT_RATIO;
+        }
+
+        return 1.0;
+    }
+
     fn update_fps_text(&mut self, current_fps: f64) {
         self.texts
             .get_mut("fps")
@@ -128,19 +580,216 @@ impl GameplayScene {
         self.render_texts();
     }
 
+    fn update_toast(&mut self, frame_duration: f64) {
+        if self.toast_time > 0.0 {
+            self.toast_time -= frame_duration;
+
+            if self.toast_time > 0.0 {
+                return;
+            }
+
+            self.set_text_string("toast", String::new());
+            self.render_texts();
+        }
+
+        if let Some(toast) = self.toasts.pop_front() {
+            self.toast_time = TOAST_DURATION;
+            self.set_text_string("toast", toast);
+            self.render_texts();
+        }
+    }
+
+    fn fill_ratio(&self) -> f32 {
+        let area_width = self.gameplay_area_extension.x() - self.gameplay_area_origin.x();
+        let area_height = self.gameplay_area_extension.y() - self.gameplay_area_origin.y();
+
+        return self.snake.body().len() as f32 / (area_width as f32 * area_height as f32);
+    }
+
+    fn check_achievements(&mut self) {
+        let unlocked = self.achievements.check(
+            &self.statistics,
+            self.score,
+            self.snake.body().len() as u32,
+            self.fill_ratio(),
+            self.play_time,
+        );
+
+        for achievement in unlocked {
+            self.toasts
+                .push_back(format!("Achievement unlocked: {}", achievement.title()));
+
+            if let Some(skin) = Skin::unlocked_by(achievement) {
+                self.toasts
+                    .push_back(format!("Skin unlocked: {}", skin.name()));
+            }
+        }
+    }
+
+    fn reset_phase(&mut self) {
+        self.phase = Phase::Playing;
+        self.phase_time = 0.0;
+        self.pending_end = Event::None;
+        self.replay = ReplayBuffer::new((REPLAY_SECONDS * self.snake.speed()) as usize);
+    }
+
+    /// Holds the snake still for the seconds in the settings, if any.
+    fn start_countdown(&mut self) {
+        let seconds = self.settings.countdown();
+
+        if seconds == 0 {
+            return;
+        }
+
+        self.phase = Phase::CountingDown;
+        self.phase_time = seconds as f64;
+        self.set_countdown_string(seconds.to_string());
+    }
+
+    /// Shows the seconds left and lets the keys pick the direction the snake starts moving in.
+    fn update_countdown(&mut self, pressed_key: Option<Key>, frame_duration: f64) {
+        self.phase_time -= frame_duration;
+        self.snake.update(pressed_key);
+
+        let seconds_left = if self.phase_time > 0.0 {
+            (self.phase_time.ceil() as u32).to_string()
+        } else {
+            String::new()
+        };
+
+        if seconds_left != self.countdown.string() {
+            self.set_countdown_string(seconds_left);
+        }
+
+        if self.phase_time <= 0.0 {
+            self.phase = Phase::Playing;
+            self.phase_time = 0.0;
+        }
+    }
+
+    /// The old digits are covered by writing the arena again instead of erasing them.
+    fn set_countdown_string(&mut self, string: String) {
+        self.countdown.set_string(string);
+        self.countdown.discard_erased();
+        self.cell_matrix.redraw();
+    }
+
+    /// Starts the death animation, the game ends once it is over.
+    fn die(&mut self, cause: DeathCause, collision: Vector<u16>) -> Event {
+        self.pending_end = self.end_game(cause);
+        self.collision = collision;
+        self.phase = Phase::Dying;
+        self.phase_time = 0.0;
+
+        return Event::None;
+    }
+
+    fn update_death_animation(&mut self, pressed_key: Option<Key>, frame_duration: f64) -> Event {
+        let theme = Theme::current();
+
+        self.phase_time += frame_duration;
+
+        if pressed_key.is_some() {
+            return self.pending_end;
+        }
+
+        if self.phase_time < DEATH_FLASH_DURATION {
+            let flash = ((self.phase_time / DEATH_FLASH_INTERVAL) as u32).is_multiple_of(2);
+
+            self.snake
+                .set_color(if flash { theme.text() } else { theme.danger() });
+        } else {
+            // The snake dissolves from the tail until the head is gone
+            let progress =
+                ((self.phase_time - DEATH_FLASH_DURATION) / DEATH_DISSOLVE_DURATION).min(1.0);
+            let remaining =
+                ((1.0 - progress) * self.snake_length_at_death() as f64).ceil() as usize;
+
+            while self.snake.body().len() > remaining && self.snake.dissolve_tail() {}
+        }
+
+        self.snake.render(&mut self.cell_matrix);
+        self.cell_matrix.set_cell(
+            &self.collision,
+            Cell::new_typeless(Glyph::Collision.char(), theme.danger(), theme.text()),
+        );
+
+        if self.phase_time < DEATH_FLASH_DURATION + DEATH_DISSOLVE_DURATION {
+            return Event::None;
+        }
+
+        if !self.settings.death_replay() || self.replay.len() == 0 {
+            return self.pending_end;
+        }
+
+        self.phase = Phase::Replaying;
+        self.phase_time = 0.0;
+        self.toasts.clear();
+        self.toast_time = 0.0;
+        self.set_text_string("toast", "REPLAY - press any key to skip".to_string());
+
+        return Event::None;
+    }
+
+    /// Plays the last frames before the death in slow motion.
+    fn update_replay(&mut self, pressed_key: Option<Key>, frame_duration: f64) -> Event {
+        self.phase_time += frame_duration;
+
+        let frame_index = (self.phase_time * self.snake.speed() as f64 / REPLAY_SLOWDOWN) as usize;
+
+        if pressed_key.is_some() || frame_index >= self.replay.len() {
+            self.set_text_string("toast", String::new());
+            self.render_texts();
+
+            return self.pending_end;
+        }
+
+        if let Some(frame) = self.replay.frame(frame_index) {
+            self.cell_matrix.update_from(frame);
+        }
+
+        self.render_texts();
+
+        return Event::None;
+    }
+
+    fn snake_length_at_death(&self) -> u32 {
+        return match self.pending_end {
+            Event::End { length, .. } => length,
+            _ => 0,
+        };
+    }
+
+    fn end_game(&mut self, cause: DeathCause) -> Event {
+        self.death_cause = Some(cause);
+        self.finish_game();
+
+        return Event::End {
+            score: self.score,
+            length: self.snake.body().len() as u32,
+            cause,
+        };
+    }
+
     fn handle_snake_update(&mut self, head: Option<Vector<u16>>) -> Event {
-        if head.is_some() {
-            match self
-                .cell_matrix
-                .get_cell(&head.unwrap())
-                .unwrap()
-                .cell_type()
-            {
-                CellType::Solid | CellType::Snake => {
-                    return Event::End;
+        if let Some(head) = head {
+            // A head outside the matrix went through the wall
+            let Some(cell) = self.cell_matrix.get_cell(&head) else {
+                return self.die(DeathCause::Wall, head);
+            };
+
+            match cell.cell_type() {
+                CellType::Solid => {
+                    return self.die(DeathCause::Wall, head);
+                }
+                CellType::Snake => {
+                    return self.die(DeathCause::Collision, head);
+                }
+                CellType::Hazard => {
+                    return self.die(DeathCause::Hazard, head);
                 }
                 CellType::Fruit => {
-                    self.snake.grow();
+                    self.snake.grow(&self.portals);
                     self.score += 1;
                     self.update_score_text(self.score);
 
@@ -148,18 +797,63 @@ impl GameplayScene {
                         &self.cell_matrix,
                         &self.gameplay_area_origin,
                         &self.gameplay_area_extension,
+                        &self.hazards,
+                        &self.portals,
+                        &mut self.random,
                     );
                 }
                 _ => (),
             }
+
+            self.hazards.tick();
+
+            if let Some(position) = self
+                .snake
+                .body()
+                .iter()
+                .find(|position| self.hazards.occupies(position))
+            {
+                return self.die(DeathCause::Hazard, position.clone());
+            }
+
+            self.check_achievements();
         }
 
         return Event::None;
     }
 }
 
+/// Half blocks need Unicode, so the setting is ignored with the ASCII glyphs.
+fn preferred_presentation() -> Presentation {
+    if Settings::load().half_blocks() && GlyphMode::current() == GlyphMode::Unicode {
+        return Presentation::HalfBlocks;
+    }
+
+    return Presentation::Characters;
+}
+
+fn saved_presentation() -> Option<Presentation> {
+    let half_blocks = Record::load(SAVE_FILE)?.get_parsed::<bool>("half_blocks")?;
+
+    if half_blocks {
+        return Some(Presentation::HalfBlocks);
+    }
+
+    return Some(Presentation::Characters);
+}
+
 pub fn build_gameplay_scene(width: u16, height: u16) -> GameplayScene {
-    let mut gameplay_scene = GameplayScene::new("gameplay".to_string(), width, height);
+    return build_gameplay_scene_with_presentation(width, height, preferred_presentation());
+}
+
+fn build_gameplay_scene_with_presentation(
+    width: u16,
+    height: u16,
+    presentation: Presentation,
+) -> GameplayScene {
+    let theme = Theme::current();
+    let mut gameplay_scene = GameplayScene::with_presentation(width, height, presentation);
+    let height = gameplay_scene.cell_matrix.height();
 
     let score_label = Text::new(
         "score_label".to_string(),
@@ -169,8 +863,8 @@ pub fn build_gameplay_scene(width: u16, height: u16) -> GameplayScene {
         "Score: ".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
+        theme.background(),
+        theme.hud(),
     );
 
     let score = Text::new(
@@ -181,8 +875,8 @@ pub fn build_gameplay_scene(width: u16, height: u16) -> GameplayScene {
         "0000000000".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
+        theme.background(),
+        theme.hud(),
     );
 
     let fps_label = Text::new(
@@ -193,8 +887,8 @@ pub fn build_gameplay_scene(width: u16, height: u16) -> GameplayScene {
         "FPS: ".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
+        theme.background(),
+        theme.hud(),
     );
 
     let fps = Text::new(
@@ -205,16 +899,94 @@ pub fn build_gameplay_scene(width: u16, height: u16) -> GameplayScene {
         "000.00".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
+        theme.background(),
+        theme.hud(),
+    );
+
+    let toast = Text::new(
+        "toast".to_string(),
+        Vector::<i32>::new(-1, 0),
+        Orientation::BottomRight,
+        Orientation::CenterRight,
+        String::new(),
+        width,
+        height,
+        theme.background(),
+        theme.toast(),
     );
 
     gameplay_scene.add_text(score_label);
     gameplay_scene.add_text(score);
     gameplay_scene.add_text(fps_label);
     gameplay_scene.add_text(fps);
+    gameplay_scene.add_text(toast);
 
     gameplay_scene.render();
 
     return gameplay_scene;
 }
+
+#[cfg(test)]
+mod tests {
+    use termion::event::Key;
+
+    use super::{build_gameplay_scene_with_presentation, GameplayScene, Phase};
+    use crate::{
+        cells::{cell_matrix::Presentation, vector::Vector},
+        gameplay::snake::Direction,
+    };
+
+    #[test]
+    fn pending_turns_survive_a_save() {
+        let mut scene = build_gameplay_scene_with_presentation(60, 30, Presentation::Characters);
+
+        scene.start_new_game();
+        // The snake faces up and turns left before its next step
+        scene.snake.update(Some(Key::Left));
+
+        let record = scene.saved_record();
+        let mut restored = build_gameplay_scene_with_presentation(60, 30, Presentation::Characters);
+
+        assert!(restored.restore(&record));
+        assert!(restored.snake.direction() == Direction::Left);
+        assert!(restored.snake.facing() == Direction::Up);
+
+        // Turning down would go back into the neck
+        restored.snake.update(Some(Key::Down));
+
+        assert!(restored.snake.direction() == Direction::Left);
+        assert_eq!(restored.snake.body(), scene.snake.body());
+    }
+
+    #[test]
+    fn the_countdown_leaves_the_arena_unchanged() {
+        let mut scene = build_gameplay_scene_with_presentation(60, 30, Presentation::HalfBlocks);
+
+        scene.settings.set_countdown(3);
+        scene.start_new_game();
+        scene.snake.render(&mut scene.cell_matrix);
+
+        let arena = scene.cell_matrix.snapshot();
+        let unchanged = |scene: &GameplayScene| {
+            return (0..arena.height()).all(|y| {
+                (0..arena.width()).all(|x| {
+                    let position = Vector::<u16>::new(x, y);
+
+                    scene.cell_matrix.get_cell(&position) == arena.get_cell(&position)
+                })
+            });
+        };
+
+        scene.start_countdown();
+
+        // The banner is only drawn over the presented frame
+        assert!(!scene.countdown.string().is_empty());
+
+        while scene.phase == Phase::CountingDown {
+            assert!(unchanged(&scene));
+            scene.update_countdown(None, 0.25);
+        }
+
+        assert!(unchanged(&scene));
+    }
+}
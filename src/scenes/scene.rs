@@ -1,22 +1,37 @@
-use std::any::Any;
-
 use termion::event::Key;
 
 use crate::{
-    core::{events::Event, terminal::Terminal},
+    cells::cell_matrix::CellMatrix,
+    core::{events::Event, input::MouseInput, terminal::Terminal},
     ui::text::Text,
 };
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SceneId {
+    MainMenu,
+    Gameplay,
+    Paused,
+    GameOver,
+    Statistics,
+    Settings,
+}
+
 pub trait Scene {
-    fn new(name: String, width: u16, height: u16) -> Self
+    fn new(width: u16, height: u16) -> Self
     where
         Self: Sized;
-    fn name(&self) -> String;
     fn add_text(&mut self, text: Text);
     fn set_text_string(&mut self, text_name: &str, new_text: String);
+    /// Called when the scene becomes the current one because of the event. Returns a follow-up event.
+    fn enter(&mut self, event: &Event) -> Event;
     fn update(&mut self, pressed_key: Option<Key>, current_fps: f64, frame_duration: f64) -> Event;
+    /// Called before `update` when the mouse was used during the frame.
+    fn handle_mouse(&mut self, mouse_input: MouseInput) -> Event;
     fn render_texts(&mut self);
     fn render(&mut self);
     fn write(&mut self, terminal: &mut Terminal);
-    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn cell_matrix(&self) -> &CellMatrix;
+    /// Overlays are drawn on top of a dimmed snapshot of the scene below them.
+    fn is_overlay(&self) -> bool;
+    fn set_backdrop(&mut self, backdrop: CellMatrix);
 }
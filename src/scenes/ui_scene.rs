@@ -1,47 +1,130 @@
-use std::{any::Any, collections::HashMap};
+use std::collections::HashMap;
 
-use termion::event::Key;
+use termion::{color::Rgb, event::Key};
 
 use crate::{
-    cells::{cell_matrix::CellMatrix, color::Color, vector::Vector},
-    core::{events::Event, terminal::Terminal},
-    ui::{button::Button, selector::Selector, text::Text, ui_element::Orientation},
-    VERSION,
+    cells::{cell_matrix::CellMatrix, glyph::Glyph, vector::Vector},
+    core::{
+        events::Event,
+        input::MouseInput,
+        settings::{Settings, COUNTDOWNS, STARTING_LENGTHS},
+        terminal::Terminal,
+        theme::Theme,
+    },
+    gameplay::{
+        achievements::{Achievements, ACHIEVEMENTS},
+        death_cause::DeathCause,
+        game_mode::GameMode,
+        skin::Skin,
+        statistics::Statistics,
+    },
+    ui::{
+        button::Button,
+        cycle_selector::CycleSelector,
+        dialog::Dialog,
+        font::Font,
+        layout::{Alignment, Axis, Layout, LayoutNode},
+        selector::{Navigation, Selector},
+        slider::Slider,
+        table::{Column, Table},
+        text::Text,
+        text_input::TextInput,
+        toggle::Toggle,
+        tween::{Animation, Easing, Tween},
+        ui_element::Orientation,
+        widget::WidgetValue,
+    },
+    DEATH_REPLAY, EVEN_SPEED, HALF_BLOCKS, MOUSE_STEERING, SHAPE_CODING, SNAKE_SPEED, VERSION,
 };
 
-use super::scene::Scene;
+use super::{gameplay_scene::GameplayScene, scene::Scene};
+
+// Width of the labels before the fields of the main menu, with the indentation
+const MENU_LABEL_WIDTH: u16 = 6;
+
+pub type EnterHook = fn(&mut UiScene, &Event);
+pub type ChangeHook = fn(&mut UiScene) -> Event;
+
+/// Asks for confirmation before the event goes through.
+struct Confirmation {
+    event: Event,
+    title: &'static str,
+    message: &'static str,
+    color: Rgb,
+}
 
 pub struct UiScene {
-    name: String,
     cell_matrix: CellMatrix,
     texts: HashMap<String, Text>,
     selector: Selector,
+    enter_hook: Option<EnterHook>,
+    change_hook: Option<ChangeHook>,
+    overlay: bool,
+    backdrop: CellMatrix,
+    layouts: Vec<Layout>,
+    confirmations: Vec<Confirmation>,
+    dialog: Option<Dialog>,
 }
 
 impl Scene for UiScene {
-    fn new(name: String, width: u16, height: u16) -> Self {
+    fn new(width: u16, height: u16) -> Self {
         return UiScene {
-            name,
             cell_matrix: CellMatrix::new(width, height),
             texts: HashMap::new(),
             selector: Selector::new(),
+            enter_hook: None,
+            change_hook: None,
+            overlay: false,
+            backdrop: CellMatrix::new(width, height).snapshot(),
+            layouts: Vec::new(),
+            confirmations: Vec::new(),
+            dialog: None,
         };
     }
 
-    fn name(&self) -> String {
-        return self.name.clone();
-    }
-
     fn add_text(&mut self, text: Text) {
         self.texts.insert(text.name(), text);
     }
 
     fn set_text_string(&mut self, text_name: &str, new_text: String) {
         self.texts.get_mut(text_name).unwrap().set_string(new_text);
+        self.reflow();
+    }
+
+    fn enter(&mut self, event: &Event) -> Event {
+        if let Some(enter_hook) = self.enter_hook {
+            enter_hook(self, event);
+        }
+
+        return Event::None;
+    }
+
+    fn update(&mut self, pressed_key: Option<Key>, _: f64, frame_duration: f64) -> Event {
+        if self.dialog.is_some() {
+            return self.update_dialog(pressed_key, frame_duration);
+        }
+
+        let event = self.selector.update(pressed_key, &mut self.cell_matrix);
+
+        if self.animate(frame_duration) {
+            self.render_animation_frame();
+        }
+
+        return self.handle_selector_event(event);
     }
 
-    fn update(&mut self, pressed_key: Option<Key>, _: f64, _: f64) -> Event {
-        return self.selector.update(pressed_key, &mut self.cell_matrix);
+    fn handle_mouse(&mut self, mouse_input: MouseInput) -> Event {
+        if let Some(dialog) = &mut self.dialog {
+            let (event, _) = dialog.handle_mouse(mouse_input, &mut self.cell_matrix);
+
+            return self.handle_dialog_event(event);
+        }
+
+        let event = self
+            .selector
+            .handle_mouse(mouse_input, &mut self.cell_matrix);
+
+        return self.handle_selector_event(event);
     }
 
     fn render_texts(&mut self) {
@@ -51,17 +134,31 @@ impl Scene for UiScene {
     }
 
     fn render(&mut self) {
-        self.cell_matrix.clear();
+        self.reflow();
+        self.discard_erased();
+        self.cell_matrix.copy_from(&self.backdrop);
         self.render_texts();
         self.selector.render(&mut self.cell_matrix);
+
+        if let Some(dialog) = &mut self.dialog {
+            dialog.render(&mut self.cell_matrix);
+        }
     }
 
     fn write(&mut self, terminal: &mut Terminal) {
         self.cell_matrix.write(terminal);
     }
 
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        return self;
+    fn cell_matrix(&self) -> &CellMatrix {
+        return &self.cell_matrix;
+    }
+
+    fn is_overlay(&self) -> bool {
+        return self.overlay;
+    }
+
+    fn set_backdrop(&mut self, backdrop: CellMatrix) {
+        self.backdrop = backdrop;
     }
 }
 
@@ -69,10 +166,195 @@ impl UiScene {
     pub fn selector_mut(&mut self) -> &mut Selector {
         return &mut self.selector;
     }
+
+    pub fn text_mut(&mut self, text_name: &str) -> &mut Text {
+        return self.texts.get_mut(text_name).unwrap();
+    }
+
+    pub fn set_overlay(&mut self, overlay: bool) {
+        self.overlay = overlay;
+    }
+
+    /// Sets a function that updates the scene whenever it is entered.
+    pub fn set_enter_hook(&mut self, enter_hook: EnterHook) {
+        self.enter_hook = Some(enter_hook);
+    }
+
+    /// Sets a function that runs whenever a widget value is edited. Returns a follow-up event.
+    pub fn set_change_hook(&mut self, change_hook: ChangeHook) {
+        self.change_hook = Some(change_hook);
+    }
+
+    /// Opens a yes or no dialog whenever the event is triggered, the event only goes through if
+    /// the player confirms.
+    pub fn add_confirmation(
+        &mut self,
+        event: Event,
+        title: &'static str,
+        message: &'static str,
+        color: Rgb,
+    ) {
+        self.confirmations.push(Confirmation {
+            event,
+            title,
+            message,
+            color,
+        });
+    }
+
+    pub fn add_layout(&mut self, layout: Layout) {
+        self.layouts.push(layout);
+        self.reflow();
+    }
+
+    /// Moves the elements in the layouts to fit their current sizes.
+    pub fn reflow(&mut self) {
+        let width = self.cell_matrix.width();
+        let height = self.cell_matrix.height();
+        let measure = |name: &str| match self.texts.get(name) {
+            Some(text) => Some((text.width(), text.height())),
+            None => self
+                .selector
+                .widget(name)
+                .map(|widget| (widget.width(), widget.height())),
+        };
+        let placements: Vec<(String, Vector<i32>)> = self
+            .layouts
+            .iter()
+            .flat_map(|layout| layout.placements(&measure, width, height))
+            .collect();
+
+        for (name, position) in placements {
+            if let Some(text) = self.texts.get_mut(&name) {
+                text.set_position(position);
+            } else if let Some(widget) = self.selector.widget_mut(&name) {
+                widget.set_position(position);
+            }
+        }
+    }
+
+    fn update_dialog(&mut self, pressed_key: Option<Key>, frame_duration: f64) -> Event {
+        let Some(dialog) = &mut self.dialog else {
+            return Event::None;
+        };
+
+        let (event, _) = dialog.update(pressed_key, &mut self.cell_matrix);
+
+        if event == Event::None {
+            if dialog.animate(frame_duration) {
+                self.render_animation_frame();
+            }
+
+            return Event::None;
+        }
+
+        return self.handle_dialog_event(event);
+    }
+
+    /// Closes the dialog once one of its buttons was pressed.
+    fn handle_dialog_event(&mut self, event: Event) -> Event {
+        if event == Event::None {
+            return Event::None;
+        }
+
+        self.dialog = None;
+        self.render();
+
+        return if event == Event::Dismiss {
+            Event::None
+        } else {
+            event
+        };
+    }
+
+    /// Opens a dialog for events that need confirmation and runs the change hook for edits.
+    fn handle_selector_event(&mut self, event: Event) -> Event {
+        if let Some(confirmation) = self
+            .confirmations
+            .iter()
+            .find(|confirmation| confirmation.event == event)
+        {
+            let theme = Theme::current();
+
+            self.dialog = Some(Dialog::new(
+                confirmation.title.to_string(),
+                confirmation.message.to_string(),
+                // Declining comes first, so it is selected by default
+                vec![
+                    ("NO".to_string(), Event::Dismiss),
+                    ("YES".to_string(), confirmation.event),
+                ],
+                None,
+                self.cell_matrix.width(),
+                self.cell_matrix.height(),
+                theme.background(),
+                confirmation.color,
+                confirmation.color,
+                theme.background(),
+            ));
+            self.render();
+
+            return Event::None;
+        }
+
+        if event == Event::ValueChanged {
+            return match self.change_hook {
+                Some(change_hook) => change_hook(self),
+                None => Event::None,
+            };
+        }
+
+        return event;
+    }
+
+    fn animate(&mut self, frame_duration: f64) -> bool {
+        let mut animated = self.selector.animate(frame_duration);
+
+        for text in self.texts.values_mut() {
+            animated |= text.update(frame_duration);
+        }
+
+        return animated;
+    }
+
+    /// Both renders start from the backdrop, so the cells erased by moved elements are dropped
+    /// instead of being written over cells that other elements may have moved into.
+    fn discard_erased(&mut self) {
+        for text in self.texts.values_mut() {
+            text.discard_erased();
+        }
+
+        self.selector.discard_erased();
+
+        if let Some(dialog) = &mut self.dialog {
+            dialog.discard_erased();
+        }
+    }
+
+    /// Redraws the whole scene on a fresh matrix and writes only the cells that changed, so moving
+    /// elements don't leave trails.
+    fn render_animation_frame(&mut self) {
+        let mut frame = self.backdrop.snapshot();
+
+        self.discard_erased();
+
+        for text in self.texts.values_mut() {
+            text.render(&mut frame);
+        }
+
+        self.selector.render(&mut frame);
+
+        if let Some(dialog) = &mut self.dialog {
+            dialog.render(&mut frame);
+        }
+
+        self.cell_matrix.update_from(&frame);
+    }
 }
 
 pub fn build_main_menu_scene(width: u16, height: u16) -> UiScene {
-    let mut ui_scene = UiScene::new("main_menu".to_string(), width, height);
+    let theme = Theme::current();
+    let mut ui_scene = UiScene::new(width, height);
 
     let top_divider = Text::new(
         "top_divider".to_string(),
@@ -82,8 +364,8 @@ pub fn build_main_menu_scene(width: u16, height: u16) -> UiScene {
         "━".repeat(width as usize),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightGreen.to_rgb(),
+        theme.background(),
+        theme.menu(),
     );
 
     let bottom_divider = Text::new(
@@ -94,8 +376,8 @@ pub fn build_main_menu_scene(width: u16, height: u16) -> UiScene {
         "━".repeat(width as usize),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightGreen.to_rgb(),
+        theme.background(),
+        theme.menu(),
     );
 
     let back_ascii = Text::new(
@@ -123,60 +405,204 @@ pub fn build_main_menu_scene(width: u16, height: u16) -> UiScene {
         .join("\n"),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightGreen.to_rgb(),
+        theme.background(),
+        theme.menu(),
     );
 
-    let title = Text::new(
+    let title = Text::new_big(
         "title".to_string(),
         Vector::<i32>::new(0, 5),
         Orientation::Top,
         Orientation::Center,
-        [
-            "███████╗███╗   ██╗ █████╗ ██╗  ██╗███████╗",
-            "██╔════╝████╗  ██║██╔══██╗██║ ██╔╝██╔════╝",
-            "███████╗██╔██╗ ██║███████║█████╔╝ █████╗  ",
-            "╚════██║██║╚██╗██║██╔══██║██╔═██╗ ██╔══╝  ",
-            "███████║██║ ╚████║██║  ██║██║  ██╗███████╗",
-            "╚══════╝╚═╝  ╚═══╝╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝",
-        ]
-        .join("\n"),
+        "SNAKE".to_string(),
+        Font::banner(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightGreen.to_rgb(),
+        theme.background(),
+        theme.menu(),
     );
 
-    let start = Button::new(
-        "start".to_string(),
-        Vector::<i32>::new(-3, -5),
-        Orientation::Center,
-        Orientation::CenterLeft,
-        "START".to_string(),
+    let mut continue_game = Button::new(
+        "continue".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "CONTINUE".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightGreen.to_rgb(),
-        Color::LightGreen.to_rgb(),
-        Color::Black.to_rgb(),
-        Event::Start,
+        theme.background(),
+        theme.menu(),
+        theme.menu(),
+        theme.background(),
+        Event::Continue,
     );
 
-    let exit = Button::new(
+    continue_game.set_hotkey('c');
+
+    let mut classic = Button::new(
+        "classic".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "CLASSIC".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.menu(),
+        theme.menu(),
+        theme.background(),
+        Event::Start {
+            mode: GameMode::Classic,
+        },
+    );
+
+    classic.set_hotkey('l');
+
+    let mut hazards = Button::new(
+        "hazards".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "HAZARDS".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.menu(),
+        theme.menu(),
+        theme.background(),
+        Event::Start {
+            mode: GameMode::Hazards,
+        },
+    );
+
+    hazards.set_hotkey('h');
+
+    let mut portals = Button::new(
+        "portals".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "PORTALS".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.menu(),
+        theme.menu(),
+        theme.background(),
+        Event::Start {
+            mode: GameMode::Portals,
+        },
+    );
+
+    portals.set_hotkey('p');
+
+    let seed_label = Text::new(
+        "seed_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "SEED:".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.menu(),
+    );
+
+    let seed = TextInput::new(
+        "seed".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "random".to_string(),
+        10,
+        |ch| ch.is_ascii_digit(),
+        width,
+        height,
+        theme.background(),
+        theme.menu(),
+        theme.menu(),
+        theme.background(),
+    );
+
+    let skin_label = Text::new(
+        "skin_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "SKIN:".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.menu(),
+    );
+
+    // The options are the unlocked skins, which are set when entering the scene
+    let skin = CycleSelector::new(
+        "skin".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        Vec::new(),
+        0,
+        width,
+        height,
+        theme.background(),
+        theme.menu(),
+        theme.menu(),
+        theme.background(),
+    );
+
+    let mut statistics = Button::new(
+        "statistics".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "STATISTICS".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.menu(),
+        theme.menu(),
+        theme.background(),
+        Event::ShowStatistics,
+    );
+
+    statistics.set_hotkey('t');
+
+    let mut settings = Button::new(
+        "settings".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "SETTINGS".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.menu(),
+        theme.menu(),
+        theme.background(),
+        Event::ShowSettings,
+    );
+
+    settings.set_hotkey('s');
+
+    let mut exit = Button::new(
         "exit".to_string(),
-        Vector::<i32>::new(-3, -3),
-        Orientation::Center,
-        Orientation::CenterLeft,
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
         "EXIT".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightGreen.to_rgb(),
-        Color::LightGreen.to_rgb(),
-        Color::Black.to_rgb(),
+        theme.background(),
+        theme.menu(),
+        theme.menu(),
+        theme.background(),
         Event::Exit,
     );
 
+    exit.set_hotkey('x');
+
     let version = Text::new(
         "version".to_string(),
         Vector::<i32>::new(0, -3),
@@ -185,8 +611,8 @@ pub fn build_main_menu_scene(width: u16, height: u16) -> UiScene {
         VERSION.to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightCyan.to_rgb(),
+        theme.background(),
+        theme.text(),
     );
 
     let info = Text::new(
@@ -197,8 +623,8 @@ pub fn build_main_menu_scene(width: u16, height: u16) -> UiScene {
         "Written in Rust by ErFer7".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
+        theme.background(),
+        theme.text(),
     );
 
     ui_scene.add_text(top_divider);
@@ -207,19 +633,138 @@ pub fn build_main_menu_scene(width: u16, height: u16) -> UiScene {
     ui_scene.add_text(title);
     ui_scene.add_text(version);
     ui_scene.add_text(info);
+    ui_scene.add_text(seed_label);
+    ui_scene.add_text(skin_label);
 
     let selector = ui_scene.selector_mut();
 
-    selector.add_button(start);
-    selector.add_button(exit);
+    selector.add_widget(continue_game);
+    selector.add_widget(classic);
+    selector.add_widget(hazards);
+    selector.add_widget(portals);
+    selector.add_widget(seed);
+    selector.add_widget(skin);
+    selector.add_widget(statistics);
+    selector.add_widget(settings);
+    selector.add_widget(exit);
+
+    ui_scene.add_layout(Layout::new(
+        LayoutNode::stack(
+            Axis::Vertical,
+            1,
+            Alignment::Start,
+            vec![
+                LayoutNode::element("continue"),
+                LayoutNode::element("classic"),
+                LayoutNode::element("hazards"),
+                LayoutNode::element("portals"),
+                LayoutNode::stack(
+                    Axis::Horizontal,
+                    0,
+                    Alignment::Start,
+                    vec![menu_label("seed_label"), LayoutNode::element("seed")],
+                ),
+                LayoutNode::stack(
+                    Axis::Horizontal,
+                    0,
+                    Alignment::Start,
+                    vec![menu_label("skin_label"), LayoutNode::element("skin")],
+                ),
+                LayoutNode::element("statistics"),
+                LayoutNode::element("settings"),
+                LayoutNode::element("exit"),
+            ],
+        ),
+        Vector::<i32>::new(-4, -11),
+        Orientation::Center,
+        Orientation::TopLeft,
+    ));
+
+    ui_scene.set_enter_hook(|ui_scene, _| {
+        let width = ui_scene.cell_matrix().width() as i32;
+
+        let seed = Settings::load()
+            .seed()
+            .map(|seed| seed.to_string())
+            .unwrap_or_default();
+        let achievements = Achievements::load();
+        let skins = Skin::unlocked(&achievements);
+        let current_skin = Skin::current(&achievements);
+        let selector = ui_scene.selector_mut();
+
+        selector.set_widget_disabled("continue", !GameplayScene::has_save());
+        selector.set_value("seed", WidgetValue::Text(seed));
+        selector.set_options(
+            "skin",
+            skins.iter().map(|skin| skin.name().to_string()).collect(),
+        );
+        selector.set_value(
+            "skin",
+            WidgetValue::Choice(
+                skins
+                    .iter()
+                    .position(|skin| skin.id() == current_skin.id())
+                    .unwrap_or(0),
+            ),
+        );
+
+        // The title slides in from the right and the credits are typed out after it
+        let title = ui_scene.text_mut("title");
+
+        title.stop_animations();
+        title.animate(
+            Animation::Position {
+                from: Vector::<i32>::new(width, 5),
+                to: Vector::<i32>::new(0, 5),
+            },
+            Tween::new(0.8, 0.2, Easing::EaseOutCubic, false),
+        );
+
+        let info = ui_scene.text_mut("info");
+
+        info.stop_animations();
+        info.animate(
+            Animation::Reveal,
+            Tween::new(0.8, 0.8, Easing::Linear, false),
+        );
+    });
+    ui_scene.add_confirmation(Event::Exit, "EXIT", "Quit the game?", theme.menu());
+    ui_scene.set_change_hook(|ui_scene| {
+        let selector = ui_scene.selector_mut();
+        let mut settings = Settings::load();
+
+        if let Some(WidgetValue::Text(seed)) = selector.value("seed") {
+            settings.set_seed(seed.parse().ok());
+        }
+
+        if let Some(WidgetValue::Choice(index)) = selector.value("skin") {
+            if let Some(skin) = Skin::unlocked(&Achievements::load()).get(index) {
+                settings.set_skin(skin.id().to_string());
+            }
+        }
+
+        settings.save();
 
+        return Event::None;
+    });
     ui_scene.render();
 
     return ui_scene;
 }
 
+/// Label before a field of the main menu, indented like the text of the buttons. Every label takes
+/// the same width so the fields after them line up.
+fn menu_label(name: &str) -> LayoutNode {
+    let mut label = LayoutNode::padded(0, 0, 0, 1, LayoutNode::element(name));
+
+    label.set_size_limits((MENU_LABEL_WIDTH, 1), (u16::MAX, u16::MAX));
+
+    return label;
+}
+
 pub fn build_paused_scene(width: u16, height: u16) -> UiScene {
-    let mut ui_scene = UiScene::new("paused".to_string(), width, height);
+    let theme = Theme::current();
+    let mut ui_scene = UiScene::new(width, height);
 
     let top_divider = Text::new(
         "top_divider".to_string(),
@@ -229,8 +774,8 @@ pub fn build_paused_scene(width: u16, height: u16) -> UiScene {
         "━".repeat(width as usize),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
+        theme.background(),
+        theme.paused(),
     );
 
     let bottom_divider = Text::new(
@@ -241,92 +786,156 @@ pub fn build_paused_scene(width: u16, height: u16) -> UiScene {
         "━".repeat(width as usize),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
+        theme.background(),
+        theme.paused(),
     );
 
-    let title = Text::new(
+    let title = Text::new_big(
         "title".to_string(),
         Vector::<i32>::new(0, 5),
         Orientation::Top,
         Orientation::Center,
-        [
-            "██████╗  █████╗ ██╗   ██╗███████╗███████╗██████╗ ",
-            "██╔══██╗██╔══██╗██║   ██║██╔════╝██╔════╝██╔══██╗",
-            "██████╔╝███████║██║   ██║███████╗█████╗  ██║  ██║",
-            "██╔═══╝ ██╔══██║██║   ██║╚════██║██╔══╝  ██║  ██║",
-            "██║     ██║  ██║╚██████╔╝███████║███████╗██████╔╝",
-            "╚═╝     ╚═╝  ╚═╝ ╚═════╝ ╚══════╝╚══════╝╚═════╝",
-        ]
-        .join("\n"),
+        "PAUSED".to_string(),
+        Font::banner(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
+        theme.background(),
+        theme.paused(),
     );
 
-    let resume = Button::new(
+    let mut resume = Button::new(
         "resume".to_string(),
-        Vector::<i32>::new(-4, -5),
-        Orientation::Center,
-        Orientation::CenterLeft,
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
         "RESUME".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
-        Color::White.to_rgb(),
-        Color::Black.to_rgb(),
+        theme.background(),
+        theme.paused(),
+        theme.paused(),
+        theme.background(),
         Event::Resume,
     );
 
-    let restart = Button::new(
+    resume.set_hotkey('u');
+
+    let mut restart = Button::new(
         "restart".to_string(),
-        Vector::<i32>::new(-4, -3),
-        Orientation::Center,
-        Orientation::CenterLeft,
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
         "RESTART".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
-        Color::White.to_rgb(),
-        Color::Black.to_rgb(),
+        theme.background(),
+        theme.paused(),
+        theme.paused(),
+        theme.background(),
         Event::Restart,
     );
 
-    let exit = Button::new(
+    restart.set_hotkey('r');
+
+    let mut save_and_quit = Button::new(
+        "save_and_quit".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "SAVE & QUIT".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.paused(),
+        theme.paused(),
+        theme.background(),
+        Event::SaveAndQuit,
+    );
+
+    save_and_quit.set_hotkey('s');
+
+    let mut exit = Button::new(
         "exit".to_string(),
-        Vector::<i32>::new(-4, -1),
-        Orientation::Center,
-        Orientation::CenterLeft,
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
         "END".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::White.to_rgb(),
-        Color::White.to_rgb(),
-        Color::Black.to_rgb(),
-        Event::End,
+        theme.background(),
+        theme.paused(),
+        theme.paused(),
+        theme.background(),
+        Event::Abandon,
+    );
+
+    exit.set_hotkey('e');
+
+    let message = Text::new(
+        "message".to_string(),
+        Vector::<i32>::new(0, 4),
+        Orientation::Center,
+        Orientation::Center,
+        String::new(),
+        width,
+        height,
+        theme.background(),
+        theme.danger(),
     );
 
     ui_scene.add_text(top_divider);
     ui_scene.add_text(bottom_divider);
     ui_scene.add_text(title);
+    ui_scene.add_text(message);
 
     let selector = ui_scene.selector_mut();
 
-    selector.add_button(resume);
-    selector.add_button(restart);
-    selector.add_button(exit);
+    selector.add_widget(resume);
+    selector.add_widget(restart);
+    selector.add_widget(save_and_quit);
+    selector.add_widget(exit);
+    selector.set_navigation(Navigation::Grid { columns: 2 });
+
+    ui_scene.add_layout(Layout::new(
+        LayoutNode::grid(
+            2,
+            2,
+            1,
+            vec![
+                LayoutNode::element("resume"),
+                LayoutNode::element("restart"),
+                LayoutNode::element("save_and_quit"),
+                LayoutNode::element("exit"),
+            ],
+        ),
+        Vector::<i32>::new(0, -4),
+        Orientation::Center,
+        Orientation::Center,
+    ));
+
+    ui_scene.add_confirmation(
+        Event::Abandon,
+        "END",
+        "Quit and lose progress?",
+        theme.paused(),
+    );
+    ui_scene.set_enter_hook(|ui_scene, event| {
+        let message = match event {
+            Event::SaveFailed => "The game couldn't be saved",
+            _ => "",
+        };
 
+        ui_scene.set_text_string("message", message.to_string());
+    });
+    ui_scene.set_overlay(true);
     ui_scene.render();
 
     return ui_scene;
 }
 
 pub fn build_game_over_scene(width: u16, height: u16) -> UiScene {
-    let mut ui_scene = UiScene::new("game_over".to_string(), width, height);
+    let theme = Theme::current();
+    let mut ui_scene = UiScene::new(width, height);
 
     let top_divider = Text::new(
         "top_divider".to_string(),
@@ -336,8 +945,8 @@ pub fn build_game_over_scene(width: u16, height: u16) -> UiScene {
         "━".repeat(width as usize),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightRed.to_rgb(),
+        theme.background(),
+        theme.game_over(),
     );
 
     let bottom_divider = Text::new(
@@ -348,82 +957,79 @@ pub fn build_game_over_scene(width: u16, height: u16) -> UiScene {
         "━".repeat(width as usize),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightRed.to_rgb(),
+        theme.background(),
+        theme.game_over(),
     );
 
-    let title = Text::new(
+    let title = Text::new_big(
         "title".to_string(),
         Vector::<i32>::new(0, 5),
         Orientation::Top,
         Orientation::Center,
-        [
-            " ██████╗  █████╗ ███╗   ███╗███████╗     ██████╗ ██╗   ██╗███████╗██████╗ ",
-            "██╔════╝ ██╔══██╗████╗ ████║██╔════╝    ██╔═══██╗██║   ██║██╔════╝██╔══██╗",
-            "██║  ███╗███████║██╔████╔██║█████╗      ██║   ██║██║   ██║█████╗  ██████╔╝",
-            "██║   ██║██╔══██║██║╚██╔╝██║██╔══╝      ██║   ██║╚██╗ ██╔╝██╔══╝  ██╔══██╗",
-            "╚██████╔╝██║  ██║██║ ╚═╝ ██║███████╗    ╚██████╔╝ ╚████╔╝ ███████╗██║  ██║",
-            " ╚═════╝ ╚═╝  ╚═╝╚═╝     ╚═╝╚══════╝     ╚═════╝   ╚═══╝  ╚══════╝╚═╝  ╚═╝",
-        ]
-        .join("\n"),
+        "GAME OVER".to_string(),
+        Font::banner(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightRed.to_rgb(),
+        theme.background(),
+        theme.game_over(),
     );
 
-    let restart = Button::new(
+    let mut restart = Button::new(
         "restart".to_string(),
-        Vector::<i32>::new(-4, -3),
-        Orientation::Center,
-        Orientation::CenterLeft,
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
         "RESTART".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightRed.to_rgb(),
-        Color::LightRed.to_rgb(),
-        Color::Black.to_rgb(),
+        theme.background(),
+        theme.game_over(),
+        theme.game_over(),
+        theme.background(),
         Event::Restart,
     );
 
-    let menu = Button::new(
+    restart.set_hotkey('r');
+
+    let mut menu = Button::new(
         "menu".to_string(),
-        Vector::<i32>::new(-4, -1),
-        Orientation::Center,
-        Orientation::CenterLeft,
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
         "MENU".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightRed.to_rgb(),
-        Color::LightRed.to_rgb(),
-        Color::Black.to_rgb(),
+        theme.background(),
+        theme.game_over(),
+        theme.game_over(),
+        theme.background(),
         Event::GoToMenu,
     );
 
+    menu.set_hotkey('m');
+
     let score_label = Text::new(
         "score_label".to_string(),
-        Vector::<i32>::new(-8, 5),
-        Orientation::Center,
-        Orientation::CenterLeft,
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
         "Score:".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightRed.to_rgb(),
+        theme.background(),
+        theme.game_over(),
     );
 
     let score = Text::new(
         "score".to_string(),
-        Vector::<i32>::new(-1, 5),
-        Orientation::Center,
-        Orientation::CenterLeft,
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
         "0000000000".to_string(),
         width,
         height,
-        Color::Black.to_rgb(),
-        Color::LightRed.to_rgb(),
+        theme.background(),
+        theme.game_over(),
     );
 
     ui_scene.add_text(top_divider);
@@ -434,9 +1040,692 @@ pub fn build_game_over_scene(width: u16, height: u16) -> UiScene {
 
     let selector = ui_scene.selector_mut();
 
-    selector.add_button(restart);
-    selector.add_button(menu);
+    selector.add_widget(restart);
+    selector.add_widget(menu);
+
+    ui_scene.add_layout(Layout::new(
+        LayoutNode::stack(
+            Axis::Vertical,
+            1,
+            Alignment::Start,
+            vec![LayoutNode::element("restart"), LayoutNode::element("menu")],
+        ),
+        Vector::<i32>::new(-4, -4),
+        Orientation::Center,
+        Orientation::TopLeft,
+    ));
+    ui_scene.add_layout(Layout::new(
+        LayoutNode::stack(
+            Axis::Horizontal,
+            1,
+            Alignment::Start,
+            vec![
+                LayoutNode::element("score_label"),
+                LayoutNode::element("score"),
+            ],
+        ),
+        Vector::<i32>::new(-8, 5),
+        Orientation::Center,
+        Orientation::CenterLeft,
+    ));
+
+    ui_scene.set_overlay(true);
+    ui_scene.set_enter_hook(|ui_scene, event| {
+        if let Event::End { score, .. } = event {
+            let score_text = ui_scene.text_mut("score");
+
+            score_text.stop_animations();
+            score_text.animate(
+                Animation::CountUp {
+                    to: *score,
+                    digits: 10,
+                },
+                Tween::new(1.2, 0.3, Easing::EaseOutQuad, false),
+            );
+        }
+
+        let theme = Theme::current();
+        let title = ui_scene.text_mut("title");
+
+        title.stop_animations();
+        title.animate(
+            Animation::FgColor {
+                from: theme.background(),
+                to: theme.game_over(),
+            },
+            Tween::new(0.6, 0.0, Easing::EaseInQuad, false),
+        );
+    });
+    ui_scene.render();
+
+    return ui_scene;
+}
+
+pub fn build_statistics_scene(width: u16, height: u16) -> UiScene {
+    let theme = Theme::current();
+    let mut ui_scene = UiScene::new(width, height);
+
+    let top_divider = Text::new(
+        "top_divider".to_string(),
+        Vector::<i32>::new(0, 0),
+        Orientation::Top,
+        Orientation::Center,
+        "━".repeat(width as usize),
+        width,
+        height,
+        theme.background(),
+        theme.statistics(),
+    );
+
+    let bottom_divider = Text::new(
+        "bottom_divider".to_string(),
+        Vector::<i32>::new(0, 0),
+        Orientation::Bottom,
+        Orientation::Center,
+        "━".repeat(width as usize),
+        width,
+        height,
+        theme.background(),
+        theme.statistics(),
+    );
+
+    let title = Text::new_big(
+        "title".to_string(),
+        Vector::<i32>::new(0, 5),
+        Orientation::Top,
+        Orientation::Center,
+        "STATS".to_string(),
+        Font::banner(),
+        width,
+        height,
+        theme.background(),
+        theme.statistics(),
+    );
+
+    let statistics = Text::new(
+        "statistics".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        String::new(),
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+    );
+
+    let achievements = Table::new(
+        "achievements".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        vec![
+            Column::new("", 3, Alignment::Center),
+            Column::new("ACHIEVEMENT", 12, Alignment::Start),
+            Column::new("DESCRIPTION", 23, Alignment::Start),
+        ],
+        8,
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+        theme.statistics(),
+        theme.background(),
+    );
+
+    let mut back = Button::new(
+        "back".to_string(),
+        Vector::<i32>::new(0, -3),
+        Orientation::Bottom,
+        Orientation::Center,
+        "BACK".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.statistics(),
+        theme.statistics(),
+        theme.background(),
+        Event::GoToMenu,
+    );
+
+    back.set_hotkey('b');
+
+    ui_scene.add_text(top_divider);
+    ui_scene.add_text(bottom_divider);
+    ui_scene.add_text(title);
+    ui_scene.add_text(statistics);
+
+    let selector = ui_scene.selector_mut();
+
+    selector.add_widget(achievements);
+    selector.add_widget(back);
+
+    ui_scene.add_layout(Layout::new(
+        LayoutNode::stack(
+            Axis::Vertical,
+            2,
+            Alignment::Center,
+            vec![
+                LayoutNode::element("statistics"),
+                LayoutNode::element("achievements"),
+            ],
+        ),
+        Vector::<i32>::new(0, 13),
+        Orientation::Top,
+        Orientation::Top,
+    ));
+
+    ui_scene.set_enter_hook(update_statistics_texts);
+    ui_scene.render();
+
+    return ui_scene;
+}
+
+fn update_statistics_texts(ui_scene: &mut UiScene, _: &Event) {
+    let statistics = Statistics::load();
+    let achievements = Achievements::load();
+    let play_time = statistics.total_play_time() as u64;
+
+    let mut statistics_lines = vec![
+        format!("Games played:  {:>10}", statistics.games_played()),
+        format!("Fruit eaten:   {:>10}", statistics.total_fruit()),
+        format!("Longest snake: {:>10}", statistics.longest_snake()),
+        format!(
+            "Play time:     {:>4}:{:02}:{:02}",
+            play_time / 3600,
+            play_time / 60 % 60,
+            play_time % 60
+        ),
+        String::new(),
+    ];
+
+    for cause in DeathCause::ALL {
+        statistics_lines.push(format!(
+            "{:<15}{:>10}",
+            format!("{}:", cause.description()),
+            statistics.deaths(cause)
+        ));
+    }
+
+    let achievements_rows: Vec<Vec<String>> = ACHIEVEMENTS
+        .iter()
+        .map(|achievement| {
+            vec![
+                if achievements.is_unlocked(achievement) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                achievement.title().to_string(),
+                achievement.description().to_string(),
+            ]
+        })
+        .collect();
+
+    ui_scene.set_text_string("statistics", statistics_lines.join("\n"));
+    ui_scene
+        .selector_mut()
+        .set_rows("achievements", achievements_rows);
+}
+
+pub fn build_settings_scene(width: u16, height: u16) -> UiScene {
+    let theme = Theme::current();
+    let mut ui_scene = UiScene::new(width, height);
+
+    let top_divider = Text::new(
+        "top_divider".to_string(),
+        Vector::<i32>::new(0, 0),
+        Orientation::Top,
+        Orientation::Center,
+        "━".repeat(width as usize),
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+    );
+
+    let bottom_divider = Text::new(
+        "bottom_divider".to_string(),
+        Vector::<i32>::new(0, 0),
+        Orientation::Bottom,
+        Orientation::Center,
+        "━".repeat(width as usize),
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+    );
+
+    let title = Text::new_big(
+        "title".to_string(),
+        Vector::<i32>::new(0, 5),
+        Orientation::Top,
+        Orientation::Center,
+        "OPTIONS".to_string(),
+        Font::banner(),
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+    );
+
+    let snake_speed_label = Text::new(
+        "snake_speed_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "Snake speed".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+    );
+
+    let snake_speed = Slider::new(
+        "snake_speed".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        SNAKE_SPEED as i32,
+        5,
+        30,
+        1,
+        16,
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+        theme.settings(),
+        theme.background(),
+    );
+
+    let starting_length_label = Text::new(
+        "starting_length_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "Starting length".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+    );
+
+    let starting_length = CycleSelector::new(
+        "starting_length".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        STARTING_LENGTHS
+            .iter()
+            .map(|length| length.to_string())
+            .collect(),
+        0,
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+        theme.settings(),
+        theme.background(),
+    );
+
+    let death_replay_label = Text::new(
+        "death_replay_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "Death replay".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+    );
+
+    let death_replay = Toggle::new(
+        "death_replay".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        DEATH_REPLAY,
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+        theme.settings(),
+        theme.background(),
+    );
+
+    let mouse_steering_label = Text::new(
+        "mouse_steering_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "Mouse steering".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+    );
+
+    let mouse_steering = Toggle::new(
+        "mouse_steering".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        MOUSE_STEERING,
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+        theme.settings(),
+        theme.background(),
+    );
+
+    let theme_label = Text::new(
+        "theme_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "Theme".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+    );
+
+    let theme_selector = CycleSelector::new(
+        "theme".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        Theme::names(),
+        0,
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+        theme.settings(),
+        theme.background(),
+    );
+
+    let shape_coding_label = Text::new(
+        "shape_coding_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "Shape coding".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+    );
+
+    let shape_coding = Toggle::new(
+        "shape_coding".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        SHAPE_CODING,
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+        theme.settings(),
+        theme.background(),
+    );
+
+    let half_blocks_label = Text::new(
+        "half_blocks_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "Square cells".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+    );
+
+    let half_blocks = Toggle::new(
+        "half_blocks".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        HALF_BLOCKS,
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+        theme.settings(),
+        theme.background(),
+    );
+
+    let even_speed_label = Text::new(
+        "even_speed_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "Even speed".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+    );
+
+    let even_speed = Toggle::new(
+        "even_speed".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        EVEN_SPEED,
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+        theme.settings(),
+        theme.background(),
+    );
+
+    let countdown_label = Text::new(
+        "countdown_label".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        "Countdown".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.text(),
+    );
+
+    let countdown = CycleSelector::new(
+        "countdown".to_string(),
+        Vector::<i32>::zero(),
+        Orientation::TopLeft,
+        Orientation::TopLeft,
+        COUNTDOWNS
+            .iter()
+            .map(|seconds| match seconds {
+                0 => "Off".to_string(),
+                _ => format!("{}s", seconds),
+            })
+            .collect(),
+        0,
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+        theme.settings(),
+        theme.background(),
+    );
+
+    let mut back = Button::new(
+        "back".to_string(),
+        Vector::<i32>::new(0, -3),
+        Orientation::Bottom,
+        Orientation::Center,
+        "BACK".to_string(),
+        width,
+        height,
+        theme.background(),
+        theme.settings(),
+        theme.settings(),
+        theme.background(),
+        Event::GoToMenu,
+    );
+
+    back.set_hotkey('b');
+
+    ui_scene.add_text(top_divider);
+    ui_scene.add_text(bottom_divider);
+    ui_scene.add_text(title);
+    ui_scene.add_text(snake_speed_label);
+    ui_scene.add_text(starting_length_label);
+    ui_scene.add_text(death_replay_label);
+    ui_scene.add_text(mouse_steering_label);
+    ui_scene.add_text(theme_label);
+    ui_scene.add_text(shape_coding_label);
+    ui_scene.add_text(half_blocks_label);
+    ui_scene.add_text(even_speed_label);
+    ui_scene.add_text(countdown_label);
+
+    let selector = ui_scene.selector_mut();
+
+    selector.add_widget(snake_speed);
+    selector.add_widget(starting_length);
+    selector.add_widget(death_replay);
+    selector.add_widget(mouse_steering);
+    selector.add_widget(theme_selector);
+    selector.add_widget(shape_coding);
+    selector.add_widget(half_blocks);
+    selector.add_widget(even_speed);
+    selector.add_widget(countdown);
+    selector.add_widget(back);
+
+    ui_scene.add_layout(Layout::new(
+        LayoutNode::grid(
+            2,
+            4,
+            1,
+            vec![
+                LayoutNode::element("snake_speed_label"),
+                LayoutNode::element("snake_speed"),
+                LayoutNode::element("starting_length_label"),
+                LayoutNode::element("starting_length"),
+                LayoutNode::element("death_replay_label"),
+                LayoutNode::element("death_replay"),
+                LayoutNode::element("mouse_steering_label"),
+                LayoutNode::element("mouse_steering"),
+                LayoutNode::element("theme_label"),
+                LayoutNode::element("theme"),
+                LayoutNode::element("shape_coding_label"),
+                LayoutNode::element("shape_coding"),
+                LayoutNode::element("half_blocks_label"),
+                LayoutNode::element("half_blocks"),
+                LayoutNode::element("even_speed_label"),
+                LayoutNode::element("even_speed"),
+                LayoutNode::element("countdown_label"),
+                LayoutNode::element("countdown"),
+            ],
+        ),
+        Vector::<i32>::new(0, 14),
+        Orientation::Top,
+        Orientation::Top,
+    ));
+
+    ui_scene.set_enter_hook(|ui_scene, event| {
+        let settings = Settings::load();
+        let starting_length = STARTING_LENGTHS
+            .iter()
+            .position(|length| *length == settings.starting_length())
+            .unwrap_or(0);
+        let countdown = COUNTDOWNS
+            .iter()
+            .position(|seconds| *seconds == settings.countdown())
+            .unwrap_or(0);
+        let theme = Theme::names()
+            .iter()
+            .position(|name| *name == settings.theme())
+            .unwrap_or(0);
+        let selector = ui_scene.selector_mut();
+
+        selector.set_value(
+            "snake_speed",
+            WidgetValue::Number(settings.snake_speed() as i32),
+        );
+        selector.set_value("starting_length", WidgetValue::Choice(starting_length));
+        selector.set_value("death_replay", WidgetValue::Toggle(settings.death_replay()));
+        selector.set_value(
+            "mouse_steering",
+            WidgetValue::Toggle(settings.mouse_steering()),
+        );
+        selector.set_value("theme", WidgetValue::Choice(theme));
+        selector.set_value("shape_coding", WidgetValue::Toggle(settings.shape_coding()));
+        selector.set_value("half_blocks", WidgetValue::Toggle(settings.half_blocks()));
+        selector.set_value("even_speed", WidgetValue::Toggle(settings.even_speed()));
+        selector.set_value("countdown", WidgetValue::Choice(countdown));
+
+        // The scene was built again with the new colors, so the theme stays selected
+        if *event == Event::ThemeChanged {
+            selector.select_widget("theme");
+        }
+    });
+    ui_scene.set_change_hook(|ui_scene| {
+        let selector = ui_scene.selector_mut();
+        let mut settings = Settings::load();
+
+        if let Some(WidgetValue::Number(snake_speed)) = selector.value("snake_speed") {
+            settings.set_snake_speed(snake_speed as f32);
+        }
+
+        if let Some(WidgetValue::Choice(index)) = selector.value("starting_length") {
+            settings.set_starting_length(STARTING_LENGTHS[index]);
+        }
+
+        if let Some(WidgetValue::Toggle(death_replay)) = selector.value("death_replay") {
+            settings.set_death_replay(death_replay);
+        }
+
+        if let Some(WidgetValue::Toggle(mouse_steering)) = selector.value("mouse_steering") {
+            settings.set_mouse_steering(mouse_steering);
+        }
+
+        if let Some(WidgetValue::Toggle(shape_coding)) = selector.value("shape_coding") {
+            settings.set_shape_coding(shape_coding);
+            Glyph::set_shape_coding(shape_coding);
+        }
+
+        if let Some(WidgetValue::Toggle(half_blocks)) = selector.value("half_blocks") {
+            settings.set_half_blocks(half_blocks);
+        }
+
+        if let Some(WidgetValue::Toggle(even_speed)) = selector.value("even_speed") {
+            settings.set_even_speed(even_speed);
+        }
+
+        if let Some(WidgetValue::Choice(index)) = selector.value("countdown") {
+            settings.set_countdown(COUNTDOWNS[index]);
+        }
+
+        let previous_theme = settings.theme();
+
+        if let Some(WidgetValue::Choice(index)) = selector.value("theme") {
+            if let Some(name) = Theme::names().get(index) {
+                settings.set_theme(name.clone());
+            }
+        }
+
+        settings.save();
+
+        if settings.theme() != previous_theme {
+            Theme::set_current(Theme::load(&settings.theme()));
+
+            return Event::ThemeChanged;
+        }
 
+        return Event::None;
+    });
     ui_scene.render();
 
     return ui_scene;
@@ -1,12 +1,20 @@
-use termion::color::Rgb;
+use termion::{color::Rgb, event::Key};
 
 use crate::{
-    cells::{cell_matrix::CellMatrix, vector::Vector},
+    cells::{cell::interpolated_color, cell_matrix::CellMatrix, vector::Vector},
     core::events::Event,
     ui::text::Text,
 };
 
-use super::ui_element::Orientation;
+use super::{
+    tween::{Animation, Easing, Tween},
+    ui_element::Orientation,
+    widget::Widget,
+};
+
+const PULSE_DURATION: f64 = 0.6;
+const PULSE_DEPTH: f32 = 0.4;
+const DISABLED_FADE: f32 = 0.6;
 
 pub struct Button {
     text: Text,
@@ -15,6 +23,8 @@ pub struct Button {
     selected_bg_color: Rgb,
     selected_fg_color: Rgb,
     event: Event,
+    hotkey: Option<char>,
+    disabled: bool,
 }
 
 impl Button {
@@ -53,24 +63,105 @@ impl Button {
             selected_bg_color,
             selected_fg_color,
             event,
+            hotkey: None,
+            disabled: false,
         };
     }
 
-    pub fn event(&self) -> Event {
-        return self.event.clone();
+    /// Sets the key that presses the button and underlines its first occurrence in the label.
+    pub fn set_hotkey(&mut self, hotkey: char) {
+        self.hotkey = Some(hotkey.to_ascii_lowercase());
+
+        // The label is padded with a space on each side
+        let index = self
+            .text
+            .string()
+            .trim()
+            .chars()
+            .position(|ch| ch.eq_ignore_ascii_case(&hotkey));
+
+        self.text
+            .set_underlined_character(index.map(|index| index + 1));
+    }
+
+    fn unselected_fg_color(&self) -> Rgb {
+        if self.disabled {
+            return interpolated_color(self.fg_color, self.bg_color, DISABLED_FADE);
+        }
+
+        return self.fg_color;
+    }
+}
+
+impl Widget for Button {
+    fn name(&self) -> String {
+        return self.text.name();
+    }
+
+    fn width(&self) -> u16 {
+        return self.text.width();
+    }
+
+    fn height(&self) -> u16 {
+        return self.text.height();
+    }
+
+    fn contains(&self, position: &Vector<u16>) -> bool {
+        return self.text.contains(position);
     }
 
-    pub fn select(&mut self) {
+    fn set_position(&mut self, position: Vector<i32>) {
+        self.text.set_position(position);
+    }
+
+    fn is_disabled(&self) -> bool {
+        return self.disabled;
+    }
+
+    fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+        self.text.set_fg_color(self.unselected_fg_color());
+    }
+
+    fn hotkey(&self) -> Option<char> {
+        return self.hotkey;
+    }
+
+    /// Selected buttons pulse towards their unselected background.
+    fn select(&mut self) {
         self.text.set_bg_color(self.selected_bg_color);
         self.text.set_fg_color(self.selected_fg_color);
+        self.text.animate(
+            Animation::BgColor {
+                from: self.selected_bg_color,
+                to: interpolated_color(self.selected_bg_color, self.bg_color, PULSE_DEPTH),
+            },
+            Tween::new(PULSE_DURATION, 0.0, Easing::EaseInOutSine, true),
+        );
     }
 
-    pub fn deselect(&mut self) {
+    fn deselect(&mut self) {
+        self.text.stop_animations();
         self.text.set_bg_color(self.bg_color);
-        self.text.set_fg_color(self.fg_color);
+        self.text.set_fg_color(self.unselected_fg_color());
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<Event> {
+        return match key {
+            Key::Char('\n') if !self.disabled => Some(self.event),
+            _ => None,
+        };
     }
 
-    pub fn render(&mut self, cell_matrix: &mut CellMatrix) {
+    fn update(&mut self, frame_duration: f64) -> bool {
+        return self.text.update(frame_duration);
+    }
+
+    fn render(&mut self, cell_matrix: &mut CellMatrix) {
         self.text.render(cell_matrix);
     }
+
+    fn discard_erased(&mut self) {
+        self.text.discard_erased();
+    }
 }
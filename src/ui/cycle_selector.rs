@@ -0,0 +1,161 @@
+use termion::{color::Rgb, event::Key};
+
+use crate::{
+    cells::{cell_matrix::CellMatrix, vector::Vector},
+    core::events::Event,
+};
+
+use super::{
+    text::Text,
+    ui_element::Orientation,
+    widget::{Widget, WidgetValue},
+};
+
+/// List of options cycled through with the left and right arrow keys, wrapping around at the ends.
+pub struct CycleSelector {
+    text: Text,
+    options: Vec<String>,
+    index: usize,
+    bg_color: Rgb,
+    fg_color: Rgb,
+    selected_bg_color: Rgb,
+    selected_fg_color: Rgb,
+}
+
+impl CycleSelector {
+    pub fn new(
+        name: String,
+        position: Vector<i32>,
+        anchor: Orientation,
+        alignment: Orientation,
+        options: Vec<String>,
+        index: usize,
+        cell_matrix_width: u16,
+        cell_matrix_height: u16,
+        bg_color: Rgb,
+        fg_color: Rgb,
+        selected_bg_color: Rgb,
+        selected_fg_color: Rgb,
+    ) -> CycleSelector {
+        let index = index.min(options.len().saturating_sub(1));
+        let text = Text::new(
+            name,
+            position,
+            anchor,
+            alignment,
+            cycle_string(&options, index),
+            cell_matrix_width,
+            cell_matrix_height,
+            bg_color,
+            fg_color,
+        );
+
+        return CycleSelector {
+            text,
+            options,
+            index,
+            bg_color,
+            fg_color,
+            selected_bg_color,
+            selected_fg_color,
+        };
+    }
+
+    fn set_index(&mut self, index: usize) {
+        self.index = index;
+        self.text
+            .set_string(cycle_string(&self.options, self.index));
+    }
+}
+
+impl Widget for CycleSelector {
+    fn name(&self) -> String {
+        return self.text.name();
+    }
+
+    fn width(&self) -> u16 {
+        return self.text.width();
+    }
+
+    fn height(&self) -> u16 {
+        return self.text.height();
+    }
+
+    fn contains(&self, position: &Vector<u16>) -> bool {
+        return self.text.contains(position);
+    }
+
+    fn set_position(&mut self, position: Vector<i32>) {
+        self.text.set_position(position);
+    }
+
+    fn select(&mut self) {
+        self.text.set_bg_color(self.selected_bg_color);
+        self.text.set_fg_color(self.selected_fg_color);
+    }
+
+    fn deselect(&mut self) {
+        self.text.set_bg_color(self.bg_color);
+        self.text.set_fg_color(self.fg_color);
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<Event> {
+        if self.options.is_empty() {
+            return None;
+        }
+
+        let index = match key {
+            Key::Left => (self.index + self.options.len() - 1) % self.options.len(),
+            Key::Right | Key::Char('\n') => (self.index + 1) % self.options.len(),
+            _ => return None,
+        };
+
+        self.set_index(index);
+
+        return Some(Event::ValueChanged);
+    }
+
+    fn update(&mut self, _: f64) -> bool {
+        return false;
+    }
+
+    fn render(&mut self, cell_matrix: &mut CellMatrix) {
+        self.text.render(cell_matrix);
+    }
+
+    fn discard_erased(&mut self) {
+        self.text.discard_erased();
+    }
+
+    fn value(&self) -> Option<WidgetValue> {
+        return Some(WidgetValue::Choice(self.index));
+    }
+
+    fn set_value(&mut self, value: WidgetValue) {
+        if let WidgetValue::Choice(index) = value {
+            if index < self.options.len() {
+                self.set_index(index);
+            }
+        }
+    }
+
+    fn set_options(&mut self, options: Vec<String>) {
+        self.options = options;
+        self.set_index(self.index.min(self.options.len().saturating_sub(1)));
+    }
+}
+
+/// Options are padded to the longest one so the arrows don't move.
+fn cycle_string(options: &[String], index: usize) -> String {
+    let width = options
+        .iter()
+        .map(|option| option.chars().count())
+        .max()
+        .unwrap_or(0);
+    let option = options
+        .get(index)
+        .map(|option| option.as_str())
+        .unwrap_or("");
+
+    return format!(" ◀ {:^width$} ▶ ", option, width = width);
+}
@@ -0,0 +1,314 @@
+use termion::{color::Rgb, event::Key};
+
+use crate::{
+    cells::{cell::Cell, cell_matrix::CellMatrix, vector::Vector},
+    core::{events::Event, input::MouseInput},
+};
+
+use super::{
+    button::Button,
+    selector::{Navigation, Selector},
+    text_input::TextInput,
+    ui_element::{Orientation, UiElement},
+    widget::{Widget, WidgetValue},
+};
+
+/// Framed box in the middle of the scene with a title, a message, an optional text field and a row
+/// of buttons. It takes every key until one of its buttons is pressed or it is dismissed with
+/// escape.
+pub struct Dialog {
+    ui_element: UiElement,
+    title: String,
+    message: String,
+    selector: Selector,
+    /// Name of the text field, if there is one.
+    text_input_name: Option<String>,
+    bg_color: Rgb,
+    fg_color: Rgb,
+}
+
+impl Dialog {
+    /// Buttons with `Event::Dismiss` close the dialog without doing anything.
+    pub fn new(
+        title: String,
+        message: String,
+        buttons: Vec<(String, Event)>,
+        text_input: Option<TextInput>,
+        cell_matrix_width: u16,
+        cell_matrix_height: u16,
+        bg_color: Rgb,
+        fg_color: Rgb,
+        selected_bg_color: Rgb,
+        selected_fg_color: Rgb,
+    ) -> Dialog {
+        let mut selector = Selector::new();
+        let mut widgets: Vec<Box<dyn Widget>> = Vec::new();
+
+        for (string, event) in buttons {
+            let hotkey = string.chars().next();
+            let mut button = Button::new(
+                string.to_lowercase(),
+                Vector::<i32>::zero(),
+                Orientation::TopLeft,
+                Orientation::TopLeft,
+                string,
+                cell_matrix_width,
+                cell_matrix_height,
+                bg_color,
+                fg_color,
+                selected_bg_color,
+                selected_fg_color,
+                event,
+            );
+
+            // Each button can be pressed with its first letter
+            if let Some(hotkey) = hotkey {
+                button.set_hotkey(hotkey);
+            }
+
+            widgets.push(Box::new(button));
+        }
+
+        let buttons_width =
+            widgets.iter().map(|widget| widget.width()).sum::<u16>() + widgets.len() as u16;
+        let input_width = text_input.as_ref().map_or(0, |input| input.width());
+        let content_width = message
+            .lines()
+            .map(|line| line.chars().count() as u16)
+            .chain([title.chars().count() as u16, buttons_width, input_width])
+            .max()
+            .unwrap_or(0);
+        let message_height = message.lines().count() as u16;
+        let input_height = if text_input.is_some() { 2 } else { 0 };
+
+        // Borders and a column of padding on each side, the title and the blank lines between
+        // each part
+        let width = content_width + 4;
+        let height = message_height + input_height + 6;
+
+        let ui_element = UiElement::new(
+            "dialog".to_string(),
+            Vector::<i32>::zero(),
+            Orientation::Center,
+            Orientation::Center,
+            width,
+            height,
+            cell_matrix_width,
+            cell_matrix_height,
+        );
+        let origin = ui_element.aligned_position();
+
+        let text_input_name = text_input.as_ref().map(|input| input.name());
+
+        // Buttons are in a row, so the horizontal arrows move between them unless a text field
+        // needs them for its cursor
+        if text_input.is_none() {
+            selector.set_navigation(Navigation::Horizontal);
+        }
+
+        if let Some(mut text_input) = text_input {
+            text_input.set_position(Vector::<i32>::new(
+                origin.x() as i32 + (width - text_input.width()) as i32 / 2,
+                origin.y() as i32 + message_height as i32 + 3,
+            ));
+            selector.add_widget(text_input);
+        }
+
+        // The buttons are centered in a row above the bottom border
+        let mut x = origin.x() as i32 + (width - buttons_width) as i32 / 2 + 1;
+
+        for mut widget in widgets {
+            widget.set_position(Vector::<i32>::new(x, (origin.y() + height - 2) as i32));
+            x += widget.width() as i32 + 1;
+            selector.add_boxed_widget(widget);
+        }
+
+        let mut dialog = Dialog {
+            ui_element,
+            title,
+            message,
+            selector,
+            text_input_name,
+            bg_color,
+            fg_color,
+        };
+
+        dialog.update_cell_group();
+
+        return dialog;
+    }
+
+    /// Returns the event of the pressed button with the text in the field, if there is one. The
+    /// event is `Event::Dismiss` if the dialog was closed and `Event::None` while it stays open.
+    pub fn update(
+        &mut self,
+        pressed_key: Option<Key>,
+        cell_matrix: &mut CellMatrix,
+    ) -> (Event, Option<String>) {
+        if pressed_key == Some(Key::Esc) {
+            return (Event::Dismiss, self.text());
+        }
+
+        let event = match self.selector.update(pressed_key, cell_matrix) {
+            Event::ValueChanged => Event::None,
+            event => event,
+        };
+
+        return (event, self.text());
+    }
+
+    /// Same as `update`, clicking outside of the frame doesn't close the dialog.
+    pub fn handle_mouse(
+        &mut self,
+        mouse_input: MouseInput,
+        cell_matrix: &mut CellMatrix,
+    ) -> (Event, Option<String>) {
+        let event = match self.selector.handle_mouse(mouse_input, cell_matrix) {
+            Event::ValueChanged => Event::None,
+            event => event,
+        };
+
+        return (event, self.text());
+    }
+
+    pub fn animate(&mut self, frame_duration: f64) -> bool {
+        return self.selector.animate(frame_duration);
+    }
+
+    pub fn render(&mut self, cell_matrix: &mut CellMatrix) {
+        self.ui_element.render(cell_matrix);
+        self.selector.render(cell_matrix);
+    }
+
+    pub fn discard_erased(&mut self) {
+        self.ui_element.discard_erased();
+        self.selector.discard_erased();
+    }
+
+    fn text(&self) -> Option<String> {
+        return match self.selector.value(self.text_input_name.as_ref()?) {
+            Some(WidgetValue::Text(text)) => Some(text),
+            _ => None,
+        };
+    }
+
+    fn update_cell_group(&mut self) {
+        let origin = self.ui_element.aligned_position();
+        let width = self.ui_element.width();
+        let height = self.ui_element.height();
+        let mut lines: Vec<(String, bool)> =
+            vec![(self.title.clone(), true), (String::new(), false)];
+
+        lines.extend(self.message.lines().map(|line| (line.to_string(), false)));
+
+        for y in 0..height {
+            for x in 0..width {
+                let ch = match (x, y) {
+                    (0, 0) => '┏',
+                    (x, 0) if x == width - 1 => '┓',
+                    (0, y) if y == height - 1 => '┗',
+                    (x, y) if x == width - 1 && y == height - 1 => '┛',
+                    (_, 0) => '━',
+                    (_, y) if y == height - 1 => '━',
+                    (0, _) => '┃',
+                    (x, _) if x == width - 1 => '┃',
+                    _ => ' ',
+                };
+
+                self.ui_element.cell_group_mut().set_cell(
+                    Vector::<u16>::new(origin.x() + x, origin.y() + y),
+                    Cell::new_typeless(ch, self.bg_color, self.fg_color),
+                );
+            }
+        }
+
+        for (i, (line, centered)) in lines.iter().enumerate() {
+            let line_width = line.chars().count() as u16;
+            let x = if *centered {
+                (width - line_width) / 2
+            } else {
+                2
+            };
+
+            for (j, ch) in line.chars().enumerate() {
+                self.ui_element.cell_group_mut().set_cell(
+                    Vector::<u16>::new(origin.x() + x + j as u16, origin.y() + 1 + i as u16),
+                    Cell::new_typeless(ch, self.bg_color, self.fg_color),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use termion::{color::Rgb, event::Key};
+
+    use super::Dialog;
+    use crate::{
+        cells::{cell_matrix::CellMatrix, vector::Vector},
+        core::events::Event,
+        ui::{text_input::TextInput, ui_element::Orientation},
+    };
+
+    fn dialog(text_input: Option<TextInput>) -> Dialog {
+        return Dialog::new(
+            "SEED".to_string(),
+            "Play with a seed?".to_string(),
+            vec![
+                ("CANCEL".to_string(), Event::Dismiss),
+                ("PLAY".to_string(), Event::Restart),
+            ],
+            text_input,
+            60,
+            30,
+            Rgb(0, 0, 0),
+            Rgb(255, 255, 255),
+            Rgb(255, 255, 255),
+            Rgb(0, 0, 0),
+        );
+    }
+
+    fn seed_input() -> TextInput {
+        return TextInput::new(
+            "seed".to_string(),
+            Vector::<i32>::zero(),
+            Orientation::TopLeft,
+            Orientation::TopLeft,
+            "random".to_string(),
+            10,
+            |ch| ch.is_ascii_digit(),
+            60,
+            30,
+            Rgb(0, 0, 0),
+            Rgb(255, 255, 255),
+            Rgb(255, 255, 255),
+            Rgb(0, 0, 0),
+        );
+    }
+
+    #[test]
+    fn text_field_value_comes_with_the_pressed_button() {
+        let mut dialog = dialog(Some(seed_input()));
+        let mut cell_matrix = CellMatrix::new(60, 30);
+        let mut press = |key: Key| dialog.update(Some(key), &mut cell_matrix);
+
+        // The field is focused first and takes letters that would be hotkeys elsewhere
+        assert!(press(Key::Char('4')) == (Event::None, Some("4".to_string())));
+        assert!(press(Key::Char('p')) == (Event::None, Some("4".to_string())));
+        assert!(press(Key::Char('2')) == (Event::None, Some("42".to_string())));
+        assert!(press(Key::Down) == (Event::None, Some("42".to_string())));
+        assert!(press(Key::Down) == (Event::None, Some("42".to_string())));
+        assert!(press(Key::Char('\n')) == (Event::Restart, Some("42".to_string())));
+    }
+
+    #[test]
+    fn dialogs_without_a_text_field_have_no_text() {
+        let mut dialog = dialog(None);
+        let mut cell_matrix = CellMatrix::new(60, 30);
+
+        assert!(dialog.update(Some(Key::Right), &mut cell_matrix) == (Event::None, None));
+        assert!(dialog.update(Some(Key::Char('\n')), &mut cell_matrix) == (Event::Restart, None));
+        assert!(dialog.update(Some(Key::Esc), &mut cell_matrix) == (Event::Dismiss, None));
+    }
+}
@@ -0,0 +1,242 @@
+use std::{collections::HashMap, fs, path::PathBuf, sync::OnceLock};
+
+use crate::{
+    core::storage::{file_path, is_valid_name},
+    BANNER_FONT,
+};
+
+const FONTS_DIRECTORY: &str = "fonts";
+const FONT_EXTENSION: &str = "flf";
+const BUNDLED_FONTS: [(&str, &str); 1] =
+    [("ansi-shadow", include_str!("../../fonts/ansi-shadow.flf"))];
+const SIGNATURE: &str = "flf2a";
+// Characters every FIGlet font defines in order, after the comments
+const REQUIRED_CHARACTERS: std::ops::RangeInclusive<u32> = 32..=126;
+
+static BANNER_FONT_NAME: OnceLock<String> = OnceLock::new();
+
+/// Block letters for large text. Fonts are FIGlet `.flf` files, drawn at full width without
+/// smushing the letters together.
+#[derive(Clone)]
+pub struct Font {
+    height: usize,
+    glyphs: HashMap<char, Vec<String>>,
+}
+
+impl Font {
+    /// Loads a bundled font or one from the fonts directory, falling back to the first bundled
+    /// font if there is neither or the file isn't a valid font.
+    pub fn load(name: &str) -> Font {
+        return Font::load_from(file_path(FONTS_DIRECTORY), name);
+    }
+
+    /// The font of the titles, picked at startup or the default one.
+    pub fn banner() -> Font {
+        return Font::load(BANNER_FONT_NAME.get().map_or(BANNER_FONT, String::as_str));
+    }
+
+    /// Only the first call has an effect.
+    pub fn set_banner(name: &str) {
+        let _ = BANNER_FONT_NAME.set(name.to_string());
+    }
+
+    fn load_from(fonts_directory: Option<PathBuf>, name: &str) -> Font {
+        let content = match BUNDLED_FONTS.iter().find(|(bundled, _)| *bundled == name) {
+            Some((_, content)) => Some(content.to_string()),
+            None if is_valid_name(name) => fonts_directory
+                .map(|directory| directory.join(format!("{}.{}", name, FONT_EXTENSION)))
+                .and_then(|path| fs::read_to_string(path).ok()),
+            None => None,
+        };
+
+        return content
+            .and_then(|content| Font::parse(&content))
+            .unwrap_or_else(|| Font::parse(BUNDLED_FONTS[0].1).unwrap());
+    }
+
+    /// Reads the header, the required characters and any code tagged ones from the content of a
+    /// FIGlet font. Returns none if the header is invalid or the font is missing a required
+    /// character.
+    pub fn parse(content: &str) -> Option<Font> {
+        let mut lines = content.lines();
+        let header = lines.next()?;
+        let hardblank = header.strip_prefix(SIGNATURE)?.chars().next()?;
+        let mut parameters = header.split_whitespace().skip(1);
+        let height: usize = parameters.next()?.parse().ok()?;
+        let comment_lines: usize = parameters.nth(3)?.parse().ok()?;
+
+        if height == 0 {
+            return None;
+        }
+
+        let mut lines = lines.skip(comment_lines);
+        let mut glyphs = HashMap::new();
+
+        for code in REQUIRED_CHARACTERS {
+            glyphs.insert(
+                char::from_u32(code)?,
+                read_glyph(&mut lines, height, hardblank)?,
+            );
+        }
+
+        // Code tagged characters start with their code, in decimal, octal or hexadecimal
+        while let Some(tag) = lines.next() {
+            let Some(glyph) = read_glyph(&mut lines, height, hardblank) else {
+                break;
+            };
+
+            if let Some(ch) = tag
+                .split_whitespace()
+                .next()
+                .and_then(parsed_code)
+                .and_then(char::from_u32)
+            {
+                glyphs.insert(ch, glyph);
+            }
+        }
+
+        return Some(Font { height, glyphs });
+    }
+
+    /// Draws each line of the string as a band of block letters. Characters missing from the
+    /// font are left out.
+    pub fn render(&self, string: &str) -> String {
+        let mut rows = Vec::new();
+
+        for line in string.lines() {
+            let mut band = vec![String::new(); self.height];
+
+            for glyph in line.chars().filter_map(|ch| self.glyphs.get(&ch)) {
+                for (row, glyph_row) in band.iter_mut().zip(glyph) {
+                    row.push_str(glyph_row);
+                }
+            }
+
+            rows.append(&mut band);
+        }
+
+        return rows.join("\n");
+    }
+}
+
+/// Takes the rows of the next character, without the end marks and with hardblanks as spaces.
+fn read_glyph<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    height: usize,
+    hardblank: char,
+) -> Option<Vec<String>> {
+    let mut glyph = Vec::with_capacity(height);
+
+    for _ in 0..height {
+        let line = lines.next()?.trim_end();
+        let end_mark = line.chars().last();
+        let row = match end_mark {
+            Some(end_mark) => line.trim_end_matches(end_mark),
+            None => line,
+        };
+
+        glyph.push(row.replace(hardblank, " "));
+    }
+
+    return Some(glyph);
+}
+
+fn parsed_code(code: &str) -> Option<u32> {
+    if let Some(hexadecimal) = code.strip_prefix("0x").or_else(|| code.strip_prefix("0X")) {
+        return u32::from_str_radix(hexadecimal, 16).ok();
+    }
+
+    if code.len() > 1 && code.starts_with('0') {
+        return u32::from_str_radix(&code[1..], 8).ok();
+    }
+
+    return code.parse().ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{parsed_code, Font, REQUIRED_CHARACTERS};
+
+    /// A font two rows high where each character is drawn as its code, with a hardblank after it.
+    fn font_content(required_characters: usize, tagged: &str) -> String {
+        let mut content = "flf2a$ 2 1 8 0 1\nA comment\n".to_string();
+
+        for code in REQUIRED_CHARACTERS.take(required_characters) {
+            content.push_str(&format!("{}$@\n{}@@\n", code, code));
+        }
+
+        content.push_str(tagged);
+
+        return content;
+    }
+
+    #[test]
+    fn parses_the_header_and_required_characters() {
+        let font = Font::parse(&font_content(REQUIRED_CHARACTERS.count(), "")).unwrap();
+
+        assert_eq!(font.height, 2);
+        assert_eq!(font.glyphs.len(), REQUIRED_CHARACTERS.count());
+        assert_eq!(font.render("AB"), "65 66 \n6566");
+        assert_eq!(font.render("A\nB"), "65 \n65\n66 \n66");
+        // Characters that the font doesn't have are left out
+        assert_eq!(font.render("A\u{1F40D}"), "65 \n65");
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        assert!(Font::parse("").is_none());
+        assert!(Font::parse("flf2b$ 2 1 8 0 1").is_none());
+        assert!(Font::parse("flf2a$ 0 1 8 0 1").is_none());
+        assert!(Font::parse("flf2a$ 2 1 8 0").is_none());
+    }
+
+    #[test]
+    fn rejects_truncated_fonts() {
+        assert!(Font::parse(&font_content(40, "")).is_none());
+        // The bundled font is used instead
+        assert!(!Font::load("missing").render("A").is_empty());
+    }
+
+    #[test]
+    fn loads_fonts_from_the_fonts_directory() {
+        let directory = env::temp_dir().join(format!("snake-fonts-{}", process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(
+            directory.join("codes.flf"),
+            font_content(REQUIRED_CHARACTERS.count(), ""),
+        )
+        .unwrap();
+
+        let font = Font::load_from(Some(directory.clone()), "codes");
+        // Names that could reach outside of the directory get the bundled font
+        let escaped = Font::load_from(Some(directory.join("inner")), "../codes");
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(font.render("A"), "65 \n65");
+        assert_ne!(escaped.render("A"), "65 \n65");
+    }
+
+    #[test]
+    fn parses_code_tagged_characters() {
+        let tagged = "196  LATIN CAPITAL LETTER A WITH DIAERESIS\nA1@\nA2@@\n\
+                      0x100 hexadecimal\nB1@\nB2@@\n\
+                      0401 octal\nC1@\nC2@@\n";
+        let font = Font::parse(&font_content(REQUIRED_CHARACTERS.count(), tagged)).unwrap();
+
+        assert_eq!(font.render("\u{C4}"), "A1\nA2");
+        assert_eq!(font.render("\u{100}"), "B1\nB2");
+        assert_eq!(font.render("\u{101}"), "C1\nC2");
+    }
+
+    #[test]
+    fn parses_codes_in_every_base() {
+        assert_eq!(parsed_code("65"), Some(65));
+        assert_eq!(parsed_code("0x41"), Some(65));
+        assert_eq!(parsed_code("0X41"), Some(65));
+        assert_eq!(parsed_code("0101"), Some(65));
+        assert_eq!(parsed_code("0"), Some(0));
+        assert_eq!(parsed_code("-1"), None);
+    }
+}
@@ -0,0 +1,529 @@
+use crate::cells::vector::Vector;
+
+use super::ui_element::{calculated_x_offset, calculated_y_offset, Orientation};
+
+#[derive(Clone, Copy)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// Where children are placed on the cross axis of a stack.
+#[derive(Clone, Copy)]
+pub enum Alignment {
+    Start,
+    Center,
+}
+
+pub enum Container {
+    Stack {
+        axis: Axis,
+        spacing: u16,
+        alignment: Alignment,
+    },
+    Grid {
+        columns: usize,
+        column_spacing: u16,
+        row_spacing: u16,
+    },
+    Padded {
+        top: u16,
+        right: u16,
+        bottom: u16,
+        left: u16,
+    },
+}
+
+/// A tree of containers whose leaves are texts or buttons referenced by name. Elements that can't
+/// be measured and containers without any measured children take no space.
+pub enum LayoutNode {
+    Element(String),
+    Container {
+        container: Container,
+        children: Vec<LayoutNode>,
+        min_size: (u16, u16),
+        max_size: (u16, u16),
+    },
+}
+
+impl LayoutNode {
+    pub fn element(name: &str) -> LayoutNode {
+        return LayoutNode::Element(name.to_string());
+    }
+
+    pub fn stack(
+        axis: Axis,
+        spacing: u16,
+        alignment: Alignment,
+        children: Vec<LayoutNode>,
+    ) -> LayoutNode {
+        return LayoutNode::container(
+            Container::Stack {
+                axis,
+                spacing,
+                alignment,
+            },
+            children,
+        );
+    }
+
+    /// Children fill the rows from left to right, each column is as wide as its widest child.
+    pub fn grid(
+        columns: usize,
+        column_spacing: u16,
+        row_spacing: u16,
+        children: Vec<LayoutNode>,
+    ) -> LayoutNode {
+        return LayoutNode::container(
+            Container::Grid {
+                columns: columns.max(1),
+                column_spacing,
+                row_spacing,
+            },
+            children,
+        );
+    }
+
+    pub fn padded(top: u16, right: u16, bottom: u16, left: u16, child: LayoutNode) -> LayoutNode {
+        return LayoutNode::container(
+            Container::Padded {
+                top,
+                right,
+                bottom,
+                left,
+            },
+            vec![child],
+        );
+    }
+
+    /// Limits the size of a container. Children that don't fit overflow it.
+    pub fn set_size_limits(&mut self, min_size: (u16, u16), max_size: (u16, u16)) {
+        if let LayoutNode::Container {
+            min_size: current_min_size,
+            max_size: current_max_size,
+            ..
+        } = self
+        {
+            *current_min_size = min_size;
+            *current_max_size = max_size;
+        }
+    }
+
+    fn container(container: Container, children: Vec<LayoutNode>) -> LayoutNode {
+        return LayoutNode::Container {
+            container,
+            children,
+            min_size: (0, 0),
+            max_size: (u16::MAX, u16::MAX),
+        };
+    }
+
+    fn size(&self, measure: &dyn Fn(&str) -> Option<(u16, u16)>) -> Option<(u16, u16)> {
+        let (container, children, min_size, max_size) = match self {
+            LayoutNode::Element(name) => return measure(name),
+            LayoutNode::Container {
+                container,
+                children,
+                min_size,
+                max_size,
+            } => (container, children, min_size, max_size),
+        };
+
+        let sizes: Vec<(u16, u16)> = children
+            .iter()
+            .filter_map(|child| child.size(measure))
+            .collect();
+
+        if sizes.is_empty() {
+            return None;
+        }
+
+        let size = match container {
+            Container::Stack { axis, spacing, .. } => {
+                let gaps = spacing * (sizes.len() as u16 - 1);
+
+                match axis {
+                    Axis::Horizontal => (
+                        sizes.iter().map(|size| size.0).sum::<u16>() + gaps,
+                        sizes.iter().map(|size| size.1).max().unwrap_or(0),
+                    ),
+                    Axis::Vertical => (
+                        sizes.iter().map(|size| size.0).max().unwrap_or(0),
+                        sizes.iter().map(|size| size.1).sum::<u16>() + gaps,
+                    ),
+                }
+            }
+            Container::Grid {
+                columns,
+                column_spacing,
+                row_spacing,
+            } => {
+                let (column_widths, row_heights) = grid_tracks(&sizes, *columns);
+
+                (
+                    column_widths.iter().sum::<u16>()
+                        + column_spacing * (column_widths.len() as u16 - 1),
+                    row_heights.iter().sum::<u16>() + row_spacing * (row_heights.len() as u16 - 1),
+                )
+            }
+            Container::Padded {
+                top,
+                right,
+                bottom,
+                left,
+            } => {
+                let child_size = sizes[0];
+
+                (child_size.0 + left + right, child_size.1 + top + bottom)
+            }
+        };
+
+        return Some((
+            size.0.clamp(min_size.0, max_size.0.max(min_size.0)),
+            size.1.clamp(min_size.1, max_size.1.max(min_size.1)),
+        ));
+    }
+
+    fn place(
+        &self,
+        origin: Vector<i32>,
+        size: (u16, u16),
+        measure: &dyn Fn(&str) -> Option<(u16, u16)>,
+        placements: &mut Vec<(String, Vector<i32>)>,
+    ) {
+        let (container, children) = match self {
+            LayoutNode::Element(name) => {
+                placements.push((name.clone(), origin));
+
+                return;
+            }
+            LayoutNode::Container {
+                container,
+                children,
+                ..
+            } => (container, children),
+        };
+
+        let visible: Vec<(&LayoutNode, (u16, u16))> = children
+            .iter()
+            .filter_map(|child| child.size(measure).map(|size| (child, size)))
+            .collect();
+
+        match container {
+            Container::Stack {
+                axis,
+                spacing,
+                alignment,
+            } => {
+                let mut offset = 0;
+
+                for (child, child_size) in visible {
+                    let child_origin = match axis {
+                        Axis::Horizontal => Vector::<i32>::new(
+                            origin.x() + offset,
+                            origin.y() + aligned_offset(*alignment, size.1, child_size.1),
+                        ),
+                        Axis::Vertical => Vector::<i32>::new(
+                            origin.x() + aligned_offset(*alignment, size.0, child_size.0),
+                            origin.y() + offset,
+                        ),
+                    };
+
+                    offset += match axis {
+                        Axis::Horizontal => child_size.0,
+                        Axis::Vertical => child_size.1,
+                    } as i32
+                        + *spacing as i32;
+
+                    child.place(child_origin, child_size, measure, placements);
+                }
+            }
+            Container::Grid {
+                columns,
+                column_spacing,
+                row_spacing,
+            } => {
+                let sizes: Vec<(u16, u16)> = visible.iter().map(|(_, size)| *size).collect();
+                let (column_widths, row_heights) = grid_tracks(&sizes, *columns);
+
+                for (i, (child, child_size)) in visible.into_iter().enumerate() {
+                    let column = i % columns;
+                    let row = i / columns;
+                    let x = column_widths[..column]
+                        .iter()
+                        .map(|width| (width + column_spacing) as i32)
+                        .sum::<i32>();
+                    let y = row_heights[..row]
+                        .iter()
+                        .map(|height| (height + row_spacing) as i32)
+                        .sum::<i32>();
+
+                    child.place(
+                        Vector::<i32>::new(origin.x() + x, origin.y() + y),
+                        child_size,
+                        measure,
+                        placements,
+                    );
+                }
+            }
+            Container::Padded { top, left, .. } => {
+                for (child, child_size) in visible {
+                    child.place(
+                        Vector::<i32>::new(origin.x() + *left as i32, origin.y() + *top as i32),
+                        child_size,
+                        measure,
+                        placements,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Places a layout tree on the cell matrix the same way a `UiElement` is placed. The elements in it
+/// should be anchored and aligned to the top left, so their positions are absolute.
+pub struct Layout {
+    root: LayoutNode,
+    position: Vector<i32>,
+    anchor: Orientation,
+    alignment: Orientation,
+}
+
+impl Layout {
+    pub fn new(
+        root: LayoutNode,
+        position: Vector<i32>,
+        anchor: Orientation,
+        alignment: Orientation,
+    ) -> Layout {
+        return Layout {
+            root,
+            position,
+            anchor,
+            alignment,
+        };
+    }
+
+    /// Computes the position of every element in the layout from the current element sizes.
+    pub fn placements(
+        &self,
+        measure: &dyn Fn(&str) -> Option<(u16, u16)>,
+        cell_matrix_width: u16,
+        cell_matrix_height: u16,
+    ) -> Vec<(String, Vector<i32>)> {
+        let mut placements = Vec::new();
+        let size = match self.root.size(measure) {
+            Some(size) => size,
+            None => return placements,
+        };
+
+        let origin = Vector::<i32>::new(
+            calculated_x_offset(&self.anchor, &self.alignment, cell_matrix_width, size.0) as i32
+                + self.position.x(),
+            calculated_y_offset(&self.anchor, &self.alignment, cell_matrix_height, size.1) as i32
+                + self.position.y(),
+        );
+
+        self.root.place(origin, size, measure, &mut placements);
+
+        return placements;
+    }
+}
+
+fn aligned_offset(alignment: Alignment, available: u16, size: u16) -> i32 {
+    return match alignment {
+        Alignment::Start => 0,
+        Alignment::Center => (available.saturating_sub(size) / 2) as i32,
+    };
+}
+
+fn grid_tracks(sizes: &[(u16, u16)], columns: usize) -> (Vec<u16>, Vec<u16>) {
+    let mut column_widths = vec![0; columns.min(sizes.len())];
+    let mut row_heights = vec![0; sizes.len().div_ceil(columns)];
+
+    for (i, size) in sizes.iter().enumerate() {
+        column_widths[i % columns] = column_widths[i % columns].max(size.0);
+        row_heights[i / columns] = row_heights[i / columns].max(size.1);
+    }
+
+    return (column_widths, row_heights);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure(name: &str) -> Option<(u16, u16)> {
+        return match name {
+            "small" => Some((2, 1)),
+            "wide" => Some((6, 1)),
+            "tall" => Some((3, 4)),
+            _ => None,
+        };
+    }
+
+    fn placements(root: &LayoutNode) -> Vec<(String, Vector<i32>)> {
+        let mut placements = Vec::new();
+        let size = root.size(&measure).unwrap();
+
+        root.place(Vector::<i32>::zero(), size, &measure, &mut placements);
+
+        return placements;
+    }
+
+    fn position(placements: &[(String, Vector<i32>)], name: &str) -> (i32, i32) {
+        let (_, position) = placements
+            .iter()
+            .find(|(placed, _)| placed == name)
+            .unwrap();
+
+        return (position.x(), position.y());
+    }
+
+    #[test]
+    fn vertical_stack_adds_heights_and_spacing() {
+        let stack = LayoutNode::stack(
+            Axis::Vertical,
+            1,
+            Alignment::Start,
+            vec![
+                LayoutNode::element("small"),
+                LayoutNode::element("tall"),
+                LayoutNode::element("wide"),
+            ],
+        );
+        let placements = placements(&stack);
+
+        assert_eq!(stack.size(&measure), Some((6, 8)));
+        assert_eq!(position(&placements, "small"), (0, 0));
+        assert_eq!(position(&placements, "tall"), (0, 2));
+        assert_eq!(position(&placements, "wide"), (0, 7));
+    }
+
+    #[test]
+    fn horizontal_stack_centers_on_the_cross_axis() {
+        let stack = LayoutNode::stack(
+            Axis::Horizontal,
+            2,
+            Alignment::Center,
+            vec![LayoutNode::element("tall"), LayoutNode::element("small")],
+        );
+        let placements = placements(&stack);
+
+        assert_eq!(stack.size(&measure), Some((7, 4)));
+        assert_eq!(position(&placements, "tall"), (0, 0));
+        assert_eq!(position(&placements, "small"), (5, 1));
+    }
+
+    #[test]
+    fn unmeasured_elements_take_no_space() {
+        let stack = LayoutNode::stack(
+            Axis::Vertical,
+            1,
+            Alignment::Start,
+            vec![
+                LayoutNode::element("small"),
+                LayoutNode::element("missing"),
+                LayoutNode::element("wide"),
+            ],
+        );
+        let placements = placements(&stack);
+
+        assert_eq!(stack.size(&measure), Some((6, 3)));
+        assert_eq!(position(&placements, "wide"), (0, 2));
+        assert!(placements.iter().all(|(name, _)| name != "missing"));
+    }
+
+    #[test]
+    fn empty_containers_take_no_space() {
+        let empty = LayoutNode::stack(
+            Axis::Vertical,
+            3,
+            Alignment::Start,
+            vec![LayoutNode::element("missing")],
+        );
+
+        assert_eq!(empty.size(&measure), None);
+        assert_eq!(LayoutNode::grid(2, 1, 1, Vec::new()).size(&measure), None);
+        assert_eq!(
+            LayoutNode::padded(1, 1, 1, 1, LayoutNode::element("missing")).size(&measure),
+            None
+        );
+
+        let stack = LayoutNode::stack(
+            Axis::Vertical,
+            1,
+            Alignment::Start,
+            vec![
+                LayoutNode::element("small"),
+                empty,
+                LayoutNode::element("small"),
+            ],
+        );
+
+        assert_eq!(stack.size(&measure), Some((2, 3)));
+    }
+
+    #[test]
+    fn grid_tracks_use_the_largest_child_of_each_column_and_row() {
+        let (column_widths, row_heights) =
+            grid_tracks(&[(2, 1), (6, 1), (3, 4), (1, 2), (5, 1)], 2);
+
+        assert_eq!(column_widths, vec![5, 6]);
+        assert_eq!(row_heights, vec![1, 4, 1]);
+        assert_eq!(grid_tracks(&[(2, 1)], 3), (vec![2], vec![1]));
+    }
+
+    #[test]
+    fn grid_places_children_in_rows() {
+        let grid = LayoutNode::grid(
+            2,
+            2,
+            1,
+            vec![
+                LayoutNode::element("small"),
+                LayoutNode::element("wide"),
+                LayoutNode::element("tall"),
+            ],
+        );
+        let placements = placements(&grid);
+
+        assert_eq!(grid.size(&measure), Some((11, 6)));
+        assert_eq!(position(&placements, "small"), (0, 0));
+        assert_eq!(position(&placements, "wide"), (5, 0));
+        assert_eq!(position(&placements, "tall"), (0, 2));
+    }
+
+    #[test]
+    fn padding_surrounds_the_child() {
+        let padded = LayoutNode::padded(1, 2, 3, 4, LayoutNode::element("wide"));
+        let placements = placements(&padded);
+
+        assert_eq!(padded.size(&measure), Some((12, 5)));
+        assert_eq!(position(&placements, "wide"), (4, 1));
+    }
+
+    #[test]
+    fn size_limits_clamp_the_size() {
+        let mut padded = LayoutNode::padded(0, 0, 0, 1, LayoutNode::element("small"));
+
+        padded.set_size_limits((6, 1), (u16::MAX, u16::MAX));
+        assert_eq!(padded.size(&measure), Some((6, 1)));
+
+        padded.set_size_limits((0, 0), (2, 1));
+        assert_eq!(padded.size(&measure), Some((2, 1)));
+    }
+
+    #[test]
+    fn layout_is_placed_like_a_ui_element() {
+        let layout = Layout::new(
+            LayoutNode::element("wide"),
+            Vector::<i32>::new(0, 2),
+            Orientation::Center,
+            Orientation::Center,
+        );
+        let placements = layout.placements(&measure, 20, 10);
+
+        assert_eq!(position(&placements, "wide"), (7, 6));
+    }
+}
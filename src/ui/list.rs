@@ -0,0 +1,252 @@
+use termion::{color::Rgb, event::Key};
+
+use crate::{
+    cells::{cell::Cell, cell_matrix::CellMatrix, vector::Vector},
+    core::events::Event,
+};
+
+use super::{
+    ui_element::{Orientation, UiElement},
+    widget::{Widget, WidgetValue},
+};
+
+/// Scrollable list of single line items with an optional header that stays in place. Items are
+/// clipped to the width of the list and the last column shows the scroll indicators.
+pub struct List {
+    ui_element: UiElement,
+    header: Option<String>,
+    items: Vec<String>,
+    visible_rows: usize,
+    offset: usize,
+    selected_index: usize,
+    bg_color: Rgb,
+    fg_color: Rgb,
+    selected_bg_color: Rgb,
+    selected_fg_color: Rgb,
+    selected: bool,
+}
+
+impl List {
+    pub fn new(
+        name: String,
+        position: Vector<i32>,
+        anchor: Orientation,
+        alignment: Orientation,
+        width: u16,
+        visible_rows: usize,
+        header: Option<String>,
+        cell_matrix_width: u16,
+        cell_matrix_height: u16,
+        bg_color: Rgb,
+        fg_color: Rgb,
+        selected_bg_color: Rgb,
+        selected_fg_color: Rgb,
+    ) -> List {
+        let height = visible_rows + header.is_some() as usize;
+        let ui_element = UiElement::new(
+            name,
+            position,
+            anchor,
+            alignment,
+            width,
+            height as u16,
+            cell_matrix_width,
+            cell_matrix_height,
+        );
+
+        let mut list = List {
+            ui_element,
+            header,
+            items: Vec::new(),
+            visible_rows,
+            offset: 0,
+            selected_index: 0,
+            bg_color,
+            fg_color,
+            selected_bg_color,
+            selected_fg_color,
+            selected: false,
+        };
+
+        list.update_cell_group();
+
+        return list;
+    }
+
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.items = items;
+        self.selected_index = self.selected_index.min(self.items.len().saturating_sub(1));
+
+        self.scroll_to_selection();
+        self.update_cell_group();
+    }
+
+    fn move_selection(&mut self, index: usize) {
+        self.selected_index = index.min(self.items.len().saturating_sub(1));
+
+        self.scroll_to_selection();
+        self.update_cell_group();
+    }
+
+    fn scroll_to_selection(&mut self) {
+        if self.selected_index < self.offset {
+            self.offset = self.selected_index;
+        } else if self.selected_index >= self.offset + self.visible_rows {
+            self.offset = self.selected_index + 1 - self.visible_rows;
+        }
+
+        self.offset = self
+            .offset
+            .min(self.items.len().saturating_sub(self.visible_rows));
+    }
+
+    fn update_cell_group(&mut self) {
+        let aligned_position = self.ui_element.aligned_position();
+        let width = self.ui_element.width() as usize;
+        let header_rows = self.header.is_some() as usize;
+        let mut rows: Vec<(String, bool)> = Vec::new();
+
+        if let Some(header) = &self.header {
+            rows.push((header.clone(), false));
+        }
+
+        for row in 0..self.visible_rows {
+            let index = self.offset + row;
+
+            rows.push((
+                self.items.get(index).cloned().unwrap_or_default(),
+                self.selected && index == self.selected_index && index < self.items.len(),
+            ));
+        }
+
+        for (y, (string, highlighted)) in rows.iter().enumerate() {
+            let (bg_color, fg_color) = if *highlighted {
+                (self.selected_bg_color, self.selected_fg_color)
+            } else {
+                (self.bg_color, self.fg_color)
+            };
+
+            // One cell of padding on the left and the scroll indicator on the right
+            let mut characters: Vec<char> = format!(" {}", string)
+                .chars()
+                .chain(std::iter::repeat(' '))
+                .take(width.saturating_sub(1))
+                .collect();
+
+            let indicator = if y == header_rows && self.offset > 0 {
+                '▲'
+            } else if y == rows.len() - 1 && self.offset + self.visible_rows < self.items.len() {
+                '▼'
+            } else {
+                ' '
+            };
+
+            characters.push(indicator);
+
+            for (x, ch) in characters.into_iter().enumerate().take(width) {
+                let cell = if x == width - 1 {
+                    Cell::new_typeless(ch, self.bg_color, self.fg_color)
+                } else {
+                    Cell::new_typeless(ch, bg_color, fg_color)
+                };
+
+                self.ui_element.cell_group_mut().set_cell(
+                    Vector::<u16>::new(
+                        aligned_position.x() + x as u16,
+                        aligned_position.y() + y as u16,
+                    ),
+                    cell,
+                );
+            }
+        }
+    }
+}
+
+impl Widget for List {
+    fn name(&self) -> String {
+        return self.ui_element.name();
+    }
+
+    fn width(&self) -> u16 {
+        return self.ui_element.width();
+    }
+
+    fn height(&self) -> u16 {
+        return self.ui_element.height();
+    }
+
+    fn contains(&self, position: &Vector<u16>) -> bool {
+        return self.ui_element.contains(position);
+    }
+
+    fn set_position(&mut self, position: Vector<i32>) {
+        self.ui_element.cell_group_mut().clear();
+        self.ui_element.set_position(position);
+
+        self.update_cell_group();
+    }
+
+    fn select(&mut self) {
+        self.selected = true;
+
+        self.update_cell_group();
+    }
+
+    fn deselect(&mut self) {
+        self.selected = false;
+
+        self.update_cell_group();
+    }
+
+    /// Up and down leave the list when they reach its ends, so the selector can move on.
+    fn handle_key(&mut self, key: Key) -> Option<Event> {
+        if self.items.is_empty() {
+            return None;
+        }
+
+        let last = self.items.len() - 1;
+        let index = match key {
+            Key::Up if self.selected_index > 0 => self.selected_index - 1,
+            Key::Down if self.selected_index < last => self.selected_index + 1,
+            Key::PageUp => self.selected_index.saturating_sub(self.visible_rows),
+            Key::PageDown => (self.selected_index + self.visible_rows).min(last),
+            Key::Home => 0,
+            Key::End => last,
+            _ => return None,
+        };
+
+        if index == self.selected_index {
+            return Some(Event::None);
+        }
+
+        self.move_selection(index);
+
+        return Some(Event::ValueChanged);
+    }
+
+    fn update(&mut self, _: f64) -> bool {
+        return false;
+    }
+
+    fn render(&mut self, cell_matrix: &mut CellMatrix) {
+        self.ui_element.render(cell_matrix);
+    }
+
+    fn discard_erased(&mut self) {
+        self.ui_element.discard_erased();
+    }
+
+    fn value(&self) -> Option<WidgetValue> {
+        return Some(WidgetValue::Choice(self.selected_index));
+    }
+
+    fn set_value(&mut self, value: WidgetValue) {
+        if let WidgetValue::Choice(index) = value {
+            self.move_selection(index);
+        }
+    }
+
+    fn set_rows(&mut self, rows: Vec<Vec<String>>) {
+        self.set_items(rows.into_iter().map(|row| row.join(" ")).collect());
+    }
+}
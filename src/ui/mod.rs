@@ -1,4 +1,15 @@
 pub mod button;
+pub mod cycle_selector;
+pub mod dialog;
+pub mod font;
+pub mod layout;
+pub mod list;
+pub mod slider;
+pub mod table;
 pub mod text;
+pub mod text_input;
+pub mod toggle;
+pub mod tween;
 pub mod ui_element;
-pub mod selector;
\ No newline at end of file
+pub mod selector;
+pub mod widget;
\ No newline at end of file
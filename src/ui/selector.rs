@@ -1,64 +1,355 @@
 use termion::event::Key;
 
-use crate::{cells::cell_matrix::CellMatrix, core::events::Event};
+use crate::{
+    cells::{cell_matrix::CellMatrix, vector::Vector},
+    core::{events::Event, input::MouseInput},
+};
 
-use super::button::Button;
+use super::widget::{Widget, WidgetValue};
+
+/// How the arrow keys move between the widgets. Moving past the last widget selects the first one
+/// and the other way around.
+#[derive(Clone, Copy)]
+pub enum Navigation {
+    Vertical,
+    Horizontal,
+    /// Widgets are laid out in rows of the given number of columns.
+    Grid {
+        columns: usize,
+    },
+}
 
 pub struct Selector {
-    buttons: Vec<Button>,
-    selected_index: usize,
+    widgets: Vec<Box<dyn Widget>>,
+    selected_index: Option<usize>,
+    navigation: Navigation,
 }
 
 impl Selector {
     pub fn new() -> Selector {
         return Selector {
-            buttons: Vec::new(),
-            selected_index: 0,
+            widgets: Vec::new(),
+            selected_index: None,
+            navigation: Navigation::Vertical,
         };
     }
 
-    pub fn add_button(&mut self, button: Button) {
-        self.buttons.push(button);
+    pub fn set_navigation(&mut self, navigation: Navigation) {
+        self.navigation = navigation;
+    }
+
+    pub fn add_widget<W: Widget + 'static>(&mut self, widget: W) {
+        self.add_boxed_widget(Box::new(widget));
+    }
+
+    pub fn add_boxed_widget(&mut self, widget: Box<dyn Widget>) {
+        self.widgets.push(widget);
+        self.fix_selection();
+    }
+
+    pub fn widget(&self, name: &str) -> Option<&dyn Widget> {
+        return self
+            .widgets
+            .iter()
+            .find(|widget| widget.name() == name)
+            .map(|widget| widget.as_ref());
+    }
+
+    pub fn widget_mut(&mut self, name: &str) -> Option<&mut Box<dyn Widget>> {
+        return self.widgets.iter_mut().find(|widget| widget.name() == name);
+    }
+
+    pub fn value(&self, name: &str) -> Option<WidgetValue> {
+        return self.widget(name).and_then(|widget| widget.value());
+    }
+
+    pub fn set_value(&mut self, name: &str, value: WidgetValue) {
+        if let Some(widget) = self.widget_mut(name) {
+            widget.set_value(value);
+        }
+    }
+
+    pub fn set_rows(&mut self, name: &str, rows: Vec<Vec<String>>) {
+        if let Some(widget) = self.widget_mut(name) {
+            widget.set_rows(rows);
+        }
+    }
+
+    pub fn set_options(&mut self, name: &str, options: Vec<String>) {
+        if let Some(widget) = self.widget_mut(name) {
+            widget.set_options(options);
+        }
+    }
 
-        if self.buttons.len() == 1 {
-            self.buttons[0].select();
+    pub fn set_widget_disabled(&mut self, name: &str, disabled: bool) {
+        if let Some(widget) = self.widget_mut(name) {
+            widget.set_disabled(disabled);
         }
+
+        self.fix_selection();
     }
 
+    pub fn select_widget(&mut self, name: &str) {
+        let Some(index) = (0..self.widgets.len())
+            .find(|&i| self.is_selectable(i) && self.widgets[i].name() == name)
+        else {
+            return;
+        };
+
+        if let Some(selected_index) = self.selected_index {
+            self.widgets[selected_index].deselect();
+        }
+
+        self.selected_index = Some(index);
+        self.widgets[index].select();
+    }
+
+    /// The selected widget gets the key first, the selector only uses the keys it doesn't, for
+    /// hotkeys and navigation.
     pub fn update(&mut self, pressed_key: Option<Key>, cell_matrix: &mut CellMatrix) -> Event {
-        match pressed_key {
-            Some(Key::Up) => {
-                if self.selected_index > 0 {
-                    self.update_selection(self.selected_index - 1, cell_matrix);
-                }
+        let (Some(key), Some(selected_index)) = (pressed_key, self.selected_index) else {
+            return Event::None;
+        };
+
+        if let Some(event) = self.widgets[selected_index].handle_key(key) {
+            self.widgets[selected_index].render(cell_matrix);
+
+            return event;
+        }
+
+        if let Key::Char(ch) = key {
+            if let Some(index) = (0..self.widgets.len()).find(|&i| {
+                self.is_selectable(i) && self.widgets[i].hotkey() == Some(ch.to_ascii_lowercase())
+            }) {
+                self.update_selection(index, cell_matrix);
+
+                return self.update(Some(Key::Char('\n')), cell_matrix);
             }
-            Some(Key::Down) => {
-                if self.selected_index < self.buttons.len() - 1 {
-                    self.update_selection(self.selected_index + 1, cell_matrix);
+        }
+
+        if let Some(index) = self.next_index(selected_index, key) {
+            self.update_selection(index, cell_matrix);
+        }
+
+        return Event::None;
+    }
+
+    /// Hovering selects a widget and clicking also presses it. The wheel works like the arrow
+    /// keys, so it scrolls the selected list before moving to the next widget.
+    pub fn handle_mouse(&mut self, mouse_input: MouseInput, cell_matrix: &mut CellMatrix) -> Event {
+        match mouse_input {
+            MouseInput::Move(position) => {
+                if let Some(index) = self.widget_index_at(&position) {
+                    if Some(index) != self.selected_index {
+                        self.update_selection(index, cell_matrix);
+                    }
                 }
+
+                return Event::None;
             }
-            Some(Key::Char('\n')) => {
-                return self.buttons[self.selected_index].event();
+            MouseInput::Click(position) => {
+                let Some(index) = self.widget_index_at(&position) else {
+                    return Event::None;
+                };
+
+                if Some(index) != self.selected_index {
+                    self.update_selection(index, cell_matrix);
+                }
+
+                return self.update(Some(Key::Char('\n')), cell_matrix);
             }
-            _ => {}
+            MouseInput::ScrollUp => return self.update(Some(Key::Up), cell_matrix),
+            MouseInput::ScrollDown => return self.update(Some(Key::Down), cell_matrix),
         }
+    }
 
-        return Event::None;
+    /// Advances the widget animations. Returns true if any of them changed.
+    pub fn animate(&mut self, frame_duration: f64) -> bool {
+        let mut animated = false;
+
+        for widget in self.widgets.iter_mut() {
+            animated |= widget.update(frame_duration);
+        }
+
+        return animated;
     }
 
     pub fn render(&mut self, cell_matrix: &mut CellMatrix) {
-        for button in self.buttons.iter_mut() {
-            button.render(cell_matrix);
+        for widget in self.widgets.iter_mut() {
+            widget.render(cell_matrix);
         }
     }
 
-    fn update_selection(&mut self, index: usize, cell_matrix: &mut CellMatrix) {
-        self.buttons[self.selected_index].deselect();
+    pub fn discard_erased(&mut self) {
+        for widget in self.widgets.iter_mut() {
+            widget.discard_erased();
+        }
+    }
 
-        self.selected_index = index;
+    fn widget_index_at(&self, position: &Vector<u16>) -> Option<usize> {
+        return (0..self.widgets.len())
+            .find(|&i| self.is_selectable(i) && self.widgets[i].contains(position));
+    }
+
+    fn is_selectable(&self, index: usize) -> bool {
+        return !self.widgets[index].is_disabled();
+    }
+
+    /// Returns the closest selectable widget in the direction of the key.
+    fn next_index(&self, selected_index: usize, key: Key) -> Option<usize> {
+        let mut index = selected_index;
+
+        for _ in 0..self.widgets.len() {
+            index = self.neighbor_index(index, key)?;
+
+            if index == selected_index {
+                return None;
+            }
+
+            if self.is_selectable(index) {
+                return Some(index);
+            }
+        }
+
+        return None;
+    }
+
+    /// Returns the widget next to the index in the direction of the key. The vertical arrows stay
+    /// in the same column of a grid.
+    fn neighbor_index(&self, index: usize, key: Key) -> Option<usize> {
+        let step = match key {
+            Key::Up | Key::Left => -1,
+            Key::Down | Key::Right => 1,
+            _ => return None,
+        };
 
-        self.buttons[self.selected_index].select();
+        return match (self.navigation, key) {
+            (Navigation::Vertical, Key::Up | Key::Down)
+            | (Navigation::Horizontal | Navigation::Grid { .. }, Key::Left | Key::Right) => {
+                Some((index as i32 + step).rem_euclid(self.widgets.len() as i32) as usize)
+            }
+            (Navigation::Grid { columns }, Key::Up | Key::Down) => {
+                let column = index % columns;
+                // The last row may not reach every column
+                let rows = (self.widgets.len() - column).div_ceil(columns) as i32;
+                let row = (index / columns) as i32 + step;
+
+                Some(row.rem_euclid(rows) as usize * columns + column)
+            }
+            _ => None,
+        };
+    }
+
+    /// Moves the selection to the first selectable widget if the selected one can't be selected
+    /// anymore.
+    fn fix_selection(&mut self) {
+        if let Some(selected_index) = self.selected_index {
+            if self.is_selectable(selected_index) {
+                return;
+            }
+
+            self.widgets[selected_index].deselect();
+        }
+
+        self.selected_index = (0..self.widgets.len()).find(|&i| self.is_selectable(i));
+
+        if let Some(selected_index) = self.selected_index {
+            self.widgets[selected_index].select();
+        }
+    }
+
+    fn update_selection(&mut self, index: usize, cell_matrix: &mut CellMatrix) {
+        if let Some(selected_index) = self.selected_index {
+            self.widgets[selected_index].deselect();
+        }
+
+        self.selected_index = Some(index);
+        self.widgets[index].select();
 
         self.render(cell_matrix);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use termion::{color::Rgb, event::Key};
+
+    use super::{Navigation, Selector};
+    use crate::{
+        cells::{cell_matrix::CellMatrix, vector::Vector},
+        core::events::Event,
+        ui::{button::Button, ui_element::Orientation},
+    };
+
+    fn selector(length: usize, navigation: Navigation) -> Selector {
+        let mut selector = Selector::new();
+
+        selector.set_navigation(navigation);
+
+        for i in 0..length {
+            selector.add_widget(Button::new(
+                i.to_string(),
+                Vector::<i32>::zero(),
+                Orientation::TopLeft,
+                Orientation::TopLeft,
+                i.to_string(),
+                20,
+                20,
+                Rgb(0, 0, 0),
+                Rgb(255, 255, 255),
+                Rgb(255, 255, 255),
+                Rgb(0, 0, 0),
+                Event::None,
+            ));
+        }
+
+        return selector;
+    }
+
+    #[test]
+    fn vertical_navigation_wraps_around() {
+        let selector = selector(3, Navigation::Vertical);
+
+        assert_eq!(selector.next_index(0, Key::Down), Some(1));
+        assert_eq!(selector.next_index(2, Key::Down), Some(0));
+        assert_eq!(selector.next_index(0, Key::Up), Some(2));
+        assert_eq!(selector.next_index(0, Key::Right), None);
+    }
+
+    #[test]
+    fn grid_navigation_wraps_within_the_column() {
+        let selector = selector(5, Navigation::Grid { columns: 3 });
+
+        assert_eq!(selector.next_index(1, Key::Up), Some(4));
+        assert_eq!(selector.next_index(4, Key::Down), Some(1));
+        assert_eq!(selector.next_index(0, Key::Down), Some(3));
+        assert_eq!(selector.next_index(2, Key::Right), Some(3));
+        assert_eq!(selector.next_index(0, Key::Left), Some(4));
+        // The last column has a single widget, so there is nothing above or below it
+        assert_eq!(selector.next_index(2, Key::Down), None);
+    }
+
+    #[test]
+    fn navigation_skips_disabled_widgets() {
+        let mut selector = selector(4, Navigation::Vertical);
+
+        selector.set_widget_disabled("1", true);
+        selector.set_widget_disabled("2", true);
+
+        assert_eq!(selector.next_index(0, Key::Down), Some(3));
+        assert_eq!(selector.next_index(3, Key::Up), Some(0));
+
+        selector.set_widget_disabled("3", true);
+
+        assert_eq!(selector.next_index(0, Key::Down), None);
+    }
+
+    #[test]
+    fn empty_selector_ignores_keys() {
+        let mut selector = selector(0, Navigation::Grid { columns: 2 });
+        let mut cell_matrix = CellMatrix::new(4, 4);
+
+        assert_eq!(selector.next_index(0, Key::Down), None);
+        assert!(selector.update(Some(Key::Down), &mut cell_matrix) == Event::None);
+    }
+}
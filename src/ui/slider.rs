@@ -0,0 +1,165 @@
+use termion::{color::Rgb, event::Key};
+
+use crate::{
+    cells::{cell_matrix::CellMatrix, vector::Vector},
+    core::events::Event,
+};
+
+use super::{
+    text::Text,
+    ui_element::Orientation,
+    widget::{Widget, WidgetValue},
+};
+
+/// Horizontal track with a numeric value, adjusted with the left and right arrow keys.
+pub struct Slider {
+    text: Text,
+    value: i32,
+    min: i32,
+    max: i32,
+    step: i32,
+    track_length: usize,
+    bg_color: Rgb,
+    fg_color: Rgb,
+    selected_bg_color: Rgb,
+    selected_fg_color: Rgb,
+}
+
+impl Slider {
+    pub fn new(
+        name: String,
+        position: Vector<i32>,
+        anchor: Orientation,
+        alignment: Orientation,
+        value: i32,
+        min: i32,
+        max: i32,
+        step: i32,
+        track_length: usize,
+        cell_matrix_width: u16,
+        cell_matrix_height: u16,
+        bg_color: Rgb,
+        fg_color: Rgb,
+        selected_bg_color: Rgb,
+        selected_fg_color: Rgb,
+    ) -> Slider {
+        let value = value.clamp(min, max);
+        let text = Text::new(
+            name,
+            position,
+            anchor,
+            alignment,
+            slider_string(value, min, max, track_length),
+            cell_matrix_width,
+            cell_matrix_height,
+            bg_color,
+            fg_color,
+        );
+
+        return Slider {
+            text,
+            value,
+            min,
+            max,
+            step,
+            track_length,
+            bg_color,
+            fg_color,
+            selected_bg_color,
+            selected_fg_color,
+        };
+    }
+
+    fn set_slider_value(&mut self, value: i32) {
+        self.value = value.clamp(self.min, self.max);
+        self.text.set_string(slider_string(
+            self.value,
+            self.min,
+            self.max,
+            self.track_length,
+        ));
+    }
+}
+
+impl Widget for Slider {
+    fn name(&self) -> String {
+        return self.text.name();
+    }
+
+    fn width(&self) -> u16 {
+        return self.text.width();
+    }
+
+    fn height(&self) -> u16 {
+        return self.text.height();
+    }
+
+    fn contains(&self, position: &Vector<u16>) -> bool {
+        return self.text.contains(position);
+    }
+
+    fn set_position(&mut self, position: Vector<i32>) {
+        self.text.set_position(position);
+    }
+
+    fn select(&mut self) {
+        self.text.set_bg_color(self.selected_bg_color);
+        self.text.set_fg_color(self.selected_fg_color);
+    }
+
+    fn deselect(&mut self) {
+        self.text.set_bg_color(self.bg_color);
+        self.text.set_fg_color(self.fg_color);
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<Event> {
+        let value = match key {
+            Key::Left => self.value - self.step,
+            Key::Right => self.value + self.step,
+            Key::Home => self.min,
+            Key::End => self.max,
+            _ => return None,
+        };
+
+        if value.clamp(self.min, self.max) == self.value {
+            return Some(Event::None);
+        }
+
+        self.set_slider_value(value);
+
+        return Some(Event::ValueChanged);
+    }
+
+    fn update(&mut self, _: f64) -> bool {
+        return false;
+    }
+
+    fn render(&mut self, cell_matrix: &mut CellMatrix) {
+        self.text.render(cell_matrix);
+    }
+
+    fn discard_erased(&mut self) {
+        self.text.discard_erased();
+    }
+
+    fn value(&self) -> Option<WidgetValue> {
+        return Some(WidgetValue::Number(self.value));
+    }
+
+    fn set_value(&mut self, value: WidgetValue) {
+        if let WidgetValue::Number(value) = value {
+            self.set_slider_value(value);
+        }
+    }
+}
+
+fn slider_string(value: i32, min: i32, max: i32, track_length: usize) -> String {
+    let digits = min.to_string().len().max(max.to_string().len());
+    let range = (max - min).max(1) as f32;
+    let knob = ((value - min) as f32 / range * (track_length - 1) as f32).round() as usize;
+    let track: String = (0..track_length)
+        .map(|i| if i == knob { '●' } else { '━' })
+        .collect();
+
+    return format!(" ◀{}▶ {:>width$} ", track, value, width = digits);
+}
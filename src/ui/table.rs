@@ -0,0 +1,163 @@
+use termion::{color::Rgb, event::Key};
+
+use crate::{
+    cells::{cell_matrix::CellMatrix, vector::Vector},
+    core::events::Event,
+};
+
+use super::{
+    layout::Alignment,
+    list::List,
+    ui_element::Orientation,
+    widget::{Widget, WidgetValue},
+};
+
+pub struct Column {
+    title: String,
+    width: usize,
+    alignment: Alignment,
+}
+
+impl Column {
+    pub fn new(title: &str, width: usize, alignment: Alignment) -> Column {
+        return Column {
+            title: title.to_string(),
+            width,
+            alignment,
+        };
+    }
+}
+
+/// Scrollable rows of cells laid out in fixed width columns under a header.
+pub struct Table {
+    list: List,
+    columns: Vec<Column>,
+}
+
+impl Table {
+    pub fn new(
+        name: String,
+        position: Vector<i32>,
+        anchor: Orientation,
+        alignment: Orientation,
+        columns: Vec<Column>,
+        visible_rows: usize,
+        cell_matrix_width: u16,
+        cell_matrix_height: u16,
+        bg_color: Rgb,
+        fg_color: Rgb,
+        selected_bg_color: Rgb,
+        selected_fg_color: Rgb,
+    ) -> Table {
+        let header = formatted_row(
+            &columns,
+            &columns
+                .iter()
+                .map(|column| column.title.clone())
+                .collect::<Vec<String>>(),
+        );
+        // The row, its left padding and the scroll indicator
+        let width = header.chars().count() as u16 + 2;
+
+        let list = List::new(
+            name,
+            position,
+            anchor,
+            alignment,
+            width,
+            visible_rows,
+            Some(header),
+            cell_matrix_width,
+            cell_matrix_height,
+            bg_color,
+            fg_color,
+            selected_bg_color,
+            selected_fg_color,
+        );
+
+        return Table { list, columns };
+    }
+}
+
+impl Widget for Table {
+    fn name(&self) -> String {
+        return self.list.name();
+    }
+
+    fn width(&self) -> u16 {
+        return self.list.width();
+    }
+
+    fn height(&self) -> u16 {
+        return self.list.height();
+    }
+
+    fn contains(&self, position: &Vector<u16>) -> bool {
+        return self.list.contains(position);
+    }
+
+    fn set_position(&mut self, position: Vector<i32>) {
+        self.list.set_position(position);
+    }
+
+    fn select(&mut self) {
+        self.list.select();
+    }
+
+    fn deselect(&mut self) {
+        self.list.deselect();
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<Event> {
+        return self.list.handle_key(key);
+    }
+
+    fn update(&mut self, frame_duration: f64) -> bool {
+        return self.list.update(frame_duration);
+    }
+
+    fn render(&mut self, cell_matrix: &mut CellMatrix) {
+        self.list.render(cell_matrix);
+    }
+
+    fn discard_erased(&mut self) {
+        self.list.discard_erased();
+    }
+
+    fn value(&self) -> Option<WidgetValue> {
+        return self.list.value();
+    }
+
+    fn set_value(&mut self, value: WidgetValue) {
+        self.list.set_value(value);
+    }
+
+    fn set_rows(&mut self, rows: Vec<Vec<String>>) {
+        self.list.set_items(
+            rows.iter()
+                .map(|row| formatted_row(&self.columns, row))
+                .collect(),
+        );
+    }
+}
+
+/// Cells are clipped or padded to the width of their columns.
+fn formatted_row(columns: &[Column], cells: &[String]) -> String {
+    return columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let cell: String = cells
+                .get(i)
+                .map(|cell| cell.chars().take(column.width).collect())
+                .unwrap_or_default();
+            let width = column.width;
+
+            match column.alignment {
+                Alignment::Start => format!("{:<width$}", cell),
+                Alignment::Center => format!("{:^width$}", cell),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+}
@@ -1,16 +1,24 @@
 use termion::color::Rgb;
 
+use crate::cells::cell::interpolated_color;
 use crate::cells::cell_group::CellGroup;
 use crate::cells::cell_matrix::CellMatrix;
 use crate::cells::{cell::Cell, vector::Vector};
 
+use super::font::Font;
+use super::tween::{interpolated_position, Animation, Tween};
 use super::ui_element::{Orientation, UiElement};
 
 pub struct Text {
     ui_element: UiElement,
     string: String,
+    /// Draws the string in block letters, for banners and countdowns.
+    font: Option<Font>,
     bg_color: Rgb,
     fg_color: Rgb,
+    visible_characters: usize,
+    underlined_character: Option<usize>,
+    animations: Vec<(Animation, Tween)>,
 }
 
 impl Text {
@@ -41,11 +49,13 @@ impl Text {
         let aligned_position = ui_element.aligned_position();
 
         update_cell_group(
-            &mut ui_element.cell_group_mut(),
+            ui_element.cell_group_mut(),
             aligned_position,
             width,
             height,
             string.clone(),
+            usize::MAX,
+            None,
             bg_color,
             fg_color,
         );
@@ -53,23 +63,74 @@ impl Text {
         return Text {
             ui_element,
             string,
+            font: None,
             bg_color,
             fg_color,
+            visible_characters: usize::MAX,
+            underlined_character: None,
+            animations: Vec::new(),
         };
     }
 
+    /// Text drawn in block letters with the font. Animations and new strings are drawn in the font
+    /// as well.
+    pub fn new_big(
+        name: String,
+        position: Vector<i32>,
+        anchor: Orientation,
+        alignment: Orientation,
+        string: String,
+        font: Font,
+        cell_matrix_width: u16,
+        cell_matrix_height: u16,
+        bg_color: Rgb,
+        fg_color: Rgb,
+    ) -> Text {
+        let mut text = Text::new(
+            name,
+            position,
+            anchor,
+            alignment,
+            font.render(&string),
+            cell_matrix_width,
+            cell_matrix_height,
+            bg_color,
+            fg_color,
+        );
+
+        text.string = string;
+        text.font = Some(font);
+
+        return text;
+    }
+
     pub fn name(&self) -> String {
         return self.ui_element.name();
     }
 
-    pub fn set_string(&mut self, string: String) {
-        let resize = self.string.len() == string.len();
+    pub fn width(&self) -> u16 {
+        return self.ui_element.width();
+    }
 
+    pub fn height(&self) -> u16 {
+        return self.ui_element.height();
+    }
+
+    pub fn contains(&self, position: &Vector<u16>) -> bool {
+        return self.ui_element.contains(position);
+    }
+
+    pub fn string(&self) -> String {
+        return self.string.clone();
+    }
+
+    pub fn set_string(&mut self, string: String) {
         self.string = string;
 
-        if resize {
-            let size = calculated_string_box_size(&self.string);
+        let size = calculated_string_box_size(&self.drawn_string());
 
+        if size.0 != self.ui_element.width() || size.1 != self.ui_element.height() {
+            self.ui_element.cell_group_mut().clear();
             self.ui_element.set_width(size.0);
             self.ui_element.set_height(size.1);
         }
@@ -89,21 +150,110 @@ impl Text {
         self.update_cell_group_wrapper();
     }
 
+    /// Underlines the character at the index, not counting line breaks.
+    pub fn set_underlined_character(&mut self, underlined_character: Option<usize>) {
+        self.underlined_character = underlined_character;
+
+        self.update_cell_group_wrapper();
+    }
+
+    pub fn set_position(&mut self, position: Vector<i32>) {
+        self.ui_element.cell_group_mut().clear();
+        self.ui_element.set_position(position);
+
+        self.update_cell_group_wrapper();
+    }
+
+    /// Starts an animation alongside the ones that are already running.
+    pub fn animate(&mut self, animation: Animation, tween: Tween) {
+        self.animations.push((animation, tween));
+        self.update(0.0);
+    }
+
+    pub fn stop_animations(&mut self) {
+        self.animations.clear();
+        self.visible_characters = usize::MAX;
+    }
+
+    /// Advances the animations. Returns true if any animation ran on this frame.
+    pub fn update(&mut self, frame_duration: f64) -> bool {
+        if self.animations.is_empty() {
+            return false;
+        }
+
+        let mut animations = std::mem::take(&mut self.animations);
+
+        for (animation, tween) in animations.iter_mut() {
+            tween.update(frame_duration);
+
+            let progress = tween.progress();
+
+            match animation {
+                Animation::Position { from, to } => {
+                    let position = interpolated_position(from, to, progress);
+
+                    if position != self.ui_element.position() {
+                        self.set_position(position);
+                    }
+                }
+                Animation::FgColor { from, to } => {
+                    self.fg_color = interpolated_color(*from, *to, progress);
+                }
+                Animation::BgColor { from, to } => {
+                    self.bg_color = interpolated_color(*from, *to, progress);
+                }
+                Animation::Reveal => {
+                    let total = self.drawn_string().chars().filter(|ch| *ch != '\n').count();
+
+                    self.visible_characters = (total as f32 * progress) as usize;
+                }
+                Animation::CountUp { to, digits } => {
+                    let value = (*to as f64 * progress as f64).round() as u32;
+
+                    self.set_string(format!("{:0width$}", value, width = *digits));
+                }
+            }
+        }
+
+        animations.retain(|(_, tween)| !tween.is_finished());
+        animations.append(&mut self.animations);
+        self.animations = animations;
+
+        self.update_cell_group_wrapper();
+
+        return true;
+    }
+
     pub fn render(&mut self, cell_matrix: &mut CellMatrix) {
         self.ui_element.render(cell_matrix);
     }
 
+    pub fn discard_erased(&mut self) {
+        self.ui_element.discard_erased();
+    }
+
+    /// The string as it is drawn, in block letters if there is a font.
+    fn drawn_string(&self) -> String {
+        return match &self.font {
+            Some(font) => font.render(&self.string),
+            None => self.string.clone(),
+        };
+    }
+
     fn update_cell_group_wrapper(&mut self) {
         let aligned_position = self.ui_element.aligned_position();
         let width = self.ui_element.width();
         let height = self.ui_element.height();
+        let string = self.drawn_string();
 
         update_cell_group(
-            &mut self.ui_element.cell_group_mut(),
+            self.ui_element.cell_group_mut(),
             aligned_position,
             width,
             height,
-            self.string.clone(),
+            string,
+            self.visible_characters,
+            self.underlined_character,
             self.bg_color,
             self.fg_color,
         );
@@ -133,29 +283,35 @@ fn update_cell_group(
     width: u16,
     height: u16,
     string: String,
+    visible_characters: usize,
+    underlined_character: Option<usize>,
     bg_color: Rgb,
     fg_color: Rgb,
 ) {
-    let mut string_index = 0;
+    let mut lines = string.lines();
+    let mut shown = 0;
 
     for y in 0..height {
+        // Shorter lines are padded so every line starts at the same column
+        let mut characters = lines.next().unwrap_or("").chars();
+
         for x in 0..width {
-            let character = string.chars().nth(string_index);
+            let cell = match characters.next() {
+                Some(ch) if shown < visible_characters => {
+                    let mut cell = Cell::new_typeless(ch, bg_color, fg_color);
 
-            if character == Some('\n') {
-                string_index += 1;
-            }
+                    cell.set_underlined(underlined_character == Some(shown));
+                    shown += 1;
 
-            let cell = match string.chars().nth(string_index) {
-                Some(ch) => Cell::new_typeless(ch, bg_color, fg_color),
-                None => Cell::new_colorless(' '),
+                    cell
+                }
+                _ => Cell::new_colorless(' '),
             };
 
             cell_group.set_cell(
                 Vector::<u16>::new(x + aligned_position.x(), y + aligned_position.y()),
                 cell,
             );
-            string_index += 1;
         }
     }
 }
@@ -0,0 +1,339 @@
+use termion::{color::Rgb, event::Key};
+
+use crate::{
+    cells::{cell::interpolated_color, cell::Cell, cell_matrix::CellMatrix, vector::Vector},
+    core::events::Event,
+};
+
+use super::{
+    ui_element::{Orientation, UiElement},
+    widget::{Widget, WidgetValue},
+};
+
+pub type CharacterFilter = fn(char) -> bool;
+
+/// Editable single line of text. Emits `Event::ValueChanged` whenever the text is edited.
+pub struct TextInput {
+    ui_element: UiElement,
+    characters: Vec<char>,
+    cursor: usize,
+    max_length: usize,
+    filter: CharacterFilter,
+    placeholder: String,
+    bg_color: Rgb,
+    fg_color: Rgb,
+    selected_bg_color: Rgb,
+    selected_fg_color: Rgb,
+    selected: bool,
+}
+
+impl TextInput {
+    pub fn new(
+        name: String,
+        position: Vector<i32>,
+        anchor: Orientation,
+        alignment: Orientation,
+        placeholder: String,
+        max_length: usize,
+        filter: CharacterFilter,
+        cell_matrix_width: u16,
+        cell_matrix_height: u16,
+        bg_color: Rgb,
+        fg_color: Rgb,
+        selected_bg_color: Rgb,
+        selected_fg_color: Rgb,
+    ) -> TextInput {
+        // One cell of padding on each side, like buttons
+        let ui_element = UiElement::new(
+            name,
+            position,
+            anchor,
+            alignment,
+            max_length as u16 + 2,
+            1,
+            cell_matrix_width,
+            cell_matrix_height,
+        );
+
+        let mut text_input = TextInput {
+            ui_element,
+            characters: Vec::new(),
+            cursor: 0,
+            max_length,
+            filter,
+            placeholder,
+            bg_color,
+            fg_color,
+            selected_bg_color,
+            selected_fg_color,
+            selected: false,
+        };
+
+        text_input.update_cell_group();
+
+        return text_input;
+    }
+
+    pub fn text(&self) -> String {
+        return self.characters.iter().collect();
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.characters = text
+            .chars()
+            .filter(|ch| (self.filter)(*ch))
+            .take(self.max_length)
+            .collect();
+        self.cursor = self.characters.len();
+
+        self.update_cell_group();
+    }
+
+    fn update_cell_group(&mut self) {
+        let (bg_color, fg_color) = if self.selected {
+            (self.selected_bg_color, self.selected_fg_color)
+        } else {
+            (self.bg_color, self.fg_color)
+        };
+        let placeholder_color = interpolated_color(fg_color, bg_color, 0.5);
+        let placeholder: Vec<char> = self.placeholder.chars().collect();
+        let aligned_position = self.ui_element.aligned_position();
+
+        for x in 0..self.ui_element.width() {
+            let index = (x as usize).wrapping_sub(1);
+            let mut cell = match self.characters.get(index) {
+                Some(ch) => Cell::new_typeless(*ch, bg_color, fg_color),
+                None if self.characters.is_empty() && index < placeholder.len() => {
+                    Cell::new_typeless(placeholder[index], bg_color, placeholder_color)
+                }
+                None => Cell::new_typeless(' ', bg_color, fg_color),
+            };
+
+            // The cursor swaps the colors of the cell under it
+            if self.selected && index == self.cursor {
+                cell = match self.characters.get(index) {
+                    Some(ch) => Cell::new_typeless(*ch, fg_color, bg_color),
+                    None => Cell::new_typeless(' ', fg_color, bg_color),
+                };
+            }
+
+            self.ui_element.cell_group_mut().set_cell(
+                Vector::<u16>::new(aligned_position.x() + x, aligned_position.y()),
+                cell,
+            );
+        }
+    }
+}
+
+impl Widget for TextInput {
+    fn name(&self) -> String {
+        return self.ui_element.name();
+    }
+
+    fn width(&self) -> u16 {
+        return self.ui_element.width();
+    }
+
+    fn height(&self) -> u16 {
+        return self.ui_element.height();
+    }
+
+    fn contains(&self, position: &Vector<u16>) -> bool {
+        return self.ui_element.contains(position);
+    }
+
+    fn set_position(&mut self, position: Vector<i32>) {
+        self.ui_element.cell_group_mut().clear();
+        self.ui_element.set_position(position);
+
+        self.update_cell_group();
+    }
+
+    fn select(&mut self) {
+        self.selected = true;
+
+        self.update_cell_group();
+    }
+
+    fn deselect(&mut self) {
+        self.selected = false;
+
+        self.update_cell_group();
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<Event> {
+        let event = match key {
+            Key::Char(ch) if ch != '\n' && !ch.is_control() => {
+                if !(self.filter)(ch) || self.characters.len() >= self.max_length {
+                    return Some(Event::None);
+                }
+
+                self.characters.insert(self.cursor, ch);
+                self.cursor += 1;
+
+                Event::ValueChanged
+            }
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.characters.remove(self.cursor);
+
+                Event::ValueChanged
+            }
+            Key::Delete if self.cursor < self.characters.len() => {
+                self.characters.remove(self.cursor);
+
+                Event::ValueChanged
+            }
+            Key::Backspace | Key::Delete => Event::None,
+            Key::Left => {
+                self.cursor = self.cursor.saturating_sub(1);
+
+                Event::None
+            }
+            Key::Right => {
+                self.cursor = (self.cursor + 1).min(self.characters.len());
+
+                Event::None
+            }
+            Key::Home => {
+                self.cursor = 0;
+
+                Event::None
+            }
+            Key::End => {
+                self.cursor = self.characters.len();
+
+                Event::None
+            }
+            _ => return None,
+        };
+
+        self.update_cell_group();
+
+        return Some(event);
+    }
+
+    fn update(&mut self, _: f64) -> bool {
+        return false;
+    }
+
+    fn render(&mut self, cell_matrix: &mut CellMatrix) {
+        self.ui_element.render(cell_matrix);
+    }
+
+    fn discard_erased(&mut self) {
+        self.ui_element.discard_erased();
+    }
+
+    fn value(&self) -> Option<WidgetValue> {
+        return Some(WidgetValue::Text(self.text()));
+    }
+
+    fn set_value(&mut self, value: WidgetValue) {
+        if let WidgetValue::Text(text) = value {
+            self.set_text(&text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_input(max_length: usize) -> TextInput {
+        let color = Rgb(0, 0, 0);
+
+        return TextInput::new(
+            "input".to_string(),
+            Vector::<i32>::zero(),
+            Orientation::TopLeft,
+            Orientation::TopLeft,
+            "placeholder".to_string(),
+            max_length,
+            |ch| ch.is_ascii_digit(),
+            80,
+            24,
+            color,
+            color,
+            color,
+            color,
+        );
+    }
+
+    fn type_keys(text_input: &mut TextInput, keys: &[Key]) {
+        for key in keys {
+            text_input.handle_key(*key);
+        }
+    }
+
+    #[test]
+    fn typing_inserts_at_the_cursor() {
+        let mut text_input = text_input(10);
+
+        type_keys(
+            &mut text_input,
+            &[
+                Key::Char('1'),
+                Key::Char('3'),
+                Key::Left,
+                Key::Char('2'),
+                Key::Home,
+                Key::Char('0'),
+                Key::End,
+                Key::Char('4'),
+            ],
+        );
+
+        assert_eq!(text_input.text(), "01234");
+    }
+
+    #[test]
+    fn backspace_and_delete_remove_around_the_cursor() {
+        let mut text_input = text_input(10);
+
+        text_input.set_text("12345");
+        type_keys(
+            &mut text_input,
+            &[Key::Backspace, Key::Left, Key::Left, Key::Delete],
+        );
+
+        assert_eq!(text_input.text(), "124");
+        assert!(text_input.handle_key(Key::Home) == Some(Event::None));
+        assert!(text_input.handle_key(Key::Backspace) == Some(Event::None));
+        assert!(text_input.handle_key(Key::Delete) == Some(Event::ValueChanged));
+        assert_eq!(text_input.text(), "24");
+    }
+
+    #[test]
+    fn filtered_characters_and_overflow_are_ignored() {
+        let mut text_input = text_input(3);
+
+        assert!(text_input.handle_key(Key::Char('a')) == Some(Event::None));
+        type_keys(
+            &mut text_input,
+            &[
+                Key::Char('1'),
+                Key::Char('2'),
+                Key::Char('3'),
+                Key::Char('4'),
+            ],
+        );
+
+        assert_eq!(text_input.text(), "123");
+
+        text_input.set_text("9a8b7c6");
+
+        assert_eq!(text_input.text(), "987");
+    }
+
+    #[test]
+    fn cursor_stays_within_the_text() {
+        let mut text_input = text_input(5);
+
+        type_keys(&mut text_input, &[Key::Left, Key::Char('1'), Key::Right]);
+        type_keys(&mut text_input, &[Key::Right, Key::Char('2')]);
+
+        assert_eq!(text_input.text(), "12");
+        assert!(text_input.handle_key(Key::Up).is_none());
+    }
+}
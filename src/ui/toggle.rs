@@ -0,0 +1,133 @@
+use termion::{color::Rgb, event::Key};
+
+use crate::{
+    cells::{cell_matrix::CellMatrix, vector::Vector},
+    core::events::Event,
+};
+
+use super::{
+    text::Text,
+    ui_element::Orientation,
+    widget::{Widget, WidgetValue},
+};
+
+/// Checkbox switched with enter, space or the arrow keys.
+pub struct Toggle {
+    text: Text,
+    checked: bool,
+    bg_color: Rgb,
+    fg_color: Rgb,
+    selected_bg_color: Rgb,
+    selected_fg_color: Rgb,
+}
+
+impl Toggle {
+    pub fn new(
+        name: String,
+        position: Vector<i32>,
+        anchor: Orientation,
+        alignment: Orientation,
+        checked: bool,
+        cell_matrix_width: u16,
+        cell_matrix_height: u16,
+        bg_color: Rgb,
+        fg_color: Rgb,
+        selected_bg_color: Rgb,
+        selected_fg_color: Rgb,
+    ) -> Toggle {
+        let text = Text::new(
+            name,
+            position,
+            anchor,
+            alignment,
+            toggle_string(checked),
+            cell_matrix_width,
+            cell_matrix_height,
+            bg_color,
+            fg_color,
+        );
+
+        return Toggle {
+            text,
+            checked,
+            bg_color,
+            fg_color,
+            selected_bg_color,
+            selected_fg_color,
+        };
+    }
+
+    fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+        self.text.set_string(toggle_string(checked));
+    }
+}
+
+impl Widget for Toggle {
+    fn name(&self) -> String {
+        return self.text.name();
+    }
+
+    fn width(&self) -> u16 {
+        return self.text.width();
+    }
+
+    fn height(&self) -> u16 {
+        return self.text.height();
+    }
+
+    fn contains(&self, position: &Vector<u16>) -> bool {
+        return self.text.contains(position);
+    }
+
+    fn set_position(&mut self, position: Vector<i32>) {
+        self.text.set_position(position);
+    }
+
+    fn select(&mut self) {
+        self.text.set_bg_color(self.selected_bg_color);
+        self.text.set_fg_color(self.selected_fg_color);
+    }
+
+    fn deselect(&mut self) {
+        self.text.set_bg_color(self.bg_color);
+        self.text.set_fg_color(self.fg_color);
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<Event> {
+        match key {
+            Key::Char('\n') | Key::Char(' ') | Key::Left | Key::Right => {
+                self.set_checked(!self.checked);
+
+                return Some(Event::ValueChanged);
+            }
+            _ => return None,
+        }
+    }
+
+    fn update(&mut self, _: f64) -> bool {
+        return false;
+    }
+
+    fn render(&mut self, cell_matrix: &mut CellMatrix) {
+        self.text.render(cell_matrix);
+    }
+
+    fn discard_erased(&mut self) {
+        self.text.discard_erased();
+    }
+
+    fn value(&self) -> Option<WidgetValue> {
+        return Some(WidgetValue::Toggle(self.checked));
+    }
+
+    fn set_value(&mut self, value: WidgetValue) {
+        if let WidgetValue::Toggle(checked) = value {
+            self.set_checked(checked);
+        }
+    }
+}
+
+fn toggle_string(checked: bool) -> String {
+    return format!(" [{}] ", if checked { 'x' } else { ' ' });
+}
@@ -0,0 +1,108 @@
+use termion::color::Rgb;
+
+use crate::cells::vector::Vector;
+
+#[derive(Clone, Copy)]
+pub enum Easing {
+    Linear,
+    EaseInQuad,
+    EaseOutQuad,
+    EaseOutCubic,
+    EaseInOutSine,
+}
+
+impl Easing {
+    /// Maps a linear progress between 0 and 1 to the eased progress.
+    pub fn apply(&self, t: f32) -> f32 {
+        return match self {
+            Easing::Linear => t,
+            Easing::EaseInQuad => t * t,
+            Easing::EaseOutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOutSine => -((std::f32::consts::PI * t).cos() - 1.0) / 2.0,
+        };
+    }
+}
+
+/// What a tween changes on a text.
+#[derive(Clone)]
+pub enum Animation {
+    Position {
+        from: Vector<i32>,
+        to: Vector<i32>,
+    },
+    FgColor {
+        from: Rgb,
+        to: Rgb,
+    },
+    BgColor {
+        from: Rgb,
+        to: Rgb,
+    },
+    /// Reveals the string one character at a time.
+    Reveal,
+    /// Counts a zero padded number up from zero.
+    CountUp {
+        to: u32,
+        digits: usize,
+    },
+}
+
+/// Progress over time, driven by the frame duration.
+#[derive(Clone)]
+pub struct Tween {
+    duration: f64,
+    delay: f64,
+    elapsed: f64,
+    easing: Easing,
+    looping: bool,
+}
+
+impl Tween {
+    /// Looping tweens go back and forth until they are removed.
+    pub fn new(duration: f64, delay: f64, easing: Easing, looping: bool) -> Tween {
+        return Tween {
+            duration,
+            delay,
+            elapsed: 0.0,
+            easing,
+            looping,
+        };
+    }
+
+    pub fn update(&mut self, frame_duration: f64) {
+        self.elapsed += frame_duration;
+    }
+
+    pub fn progress(&self) -> f32 {
+        let time = (self.elapsed - self.delay).max(0.0);
+
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        let t = if self.looping {
+            let cycle = time / self.duration % 2.0;
+
+            if cycle > 1.0 {
+                2.0 - cycle
+            } else {
+                cycle
+            }
+        } else {
+            (time / self.duration).min(1.0)
+        };
+
+        return self.easing.apply(t as f32);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        return !self.looping && self.elapsed >= self.delay + self.duration;
+    }
+}
+
+pub fn interpolated_position(from: &Vector<i32>, to: &Vector<i32>, progress: f32) -> Vector<i32> {
+    let channel = |from: i32, to: i32| (from as f32 + (to - from) as f32 * progress).round() as i32;
+
+    return Vector::<i32>::new(channel(from.x(), to.x()), channel(from.y(), to.y()));
+}
@@ -67,6 +67,32 @@ impl UiElement {
         return self.aligned_position.clone();
     }
 
+    pub fn position(&self) -> Vector<i32> {
+        return self.position.clone();
+    }
+
+    pub fn set_position(&mut self, position: Vector<i32>) {
+        self.position = position;
+
+        let x_offset = calculated_x_offset(
+            &self.anchor,
+            &self.alignment,
+            self.cell_matrix_width,
+            self.width,
+        );
+        let y_offset = calculated_y_offset(
+            &self.anchor,
+            &self.alignment,
+            self.cell_matrix_height,
+            self.height,
+        );
+
+        self.aligned_position = Vector::<u16>::new(
+            (x_offset as i32 + self.position.x()) as u16,
+            (y_offset as i32 + self.position.y()) as u16,
+        );
+    }
+
     pub fn width(&self) -> u16 {
         return self.width;
     }
@@ -103,6 +129,14 @@ impl UiElement {
             .set_y((y_offset as i32 + self.position.y()) as u16);
     }
 
+    /// Returns true if the cell is inside the element's bounds.
+    pub fn contains(&self, position: &Vector<u16>) -> bool {
+        return position.x() >= self.aligned_position.x()
+            && position.x() < self.aligned_position.x() + self.width
+            && position.y() >= self.aligned_position.y()
+            && position.y() < self.aligned_position.y() + self.height;
+    }
+
     pub fn cell_group_mut(&mut self) -> &mut CellGroup {
         return &mut self.cell_group;
     }
@@ -110,9 +144,13 @@ impl UiElement {
     pub fn render(&mut self, cell_matrix: &mut CellMatrix) {
         self.cell_group.render(cell_matrix);
     }
+
+    pub fn discard_erased(&mut self) {
+        self.cell_group.discard_erased();
+    }
 }
 
-fn calculated_x_offset(
+pub fn calculated_x_offset(
     anchor: &Orientation,
     orientation: &Orientation,
     cell_matrix_width: u16,
@@ -134,14 +172,10 @@ fn calculated_x_offset(
         Orientation::TopRight | Orientation::CenterRight | Orientation::BottomRight => width,
     };
 
-    return if anchor_offset >= orientation_offset {
-        anchor_offset - orientation_offset
-    } else {
-        0
-    };
+    return anchor_offset.saturating_sub(orientation_offset);
 }
 
-fn calculated_y_offset(
+pub fn calculated_y_offset(
     anchor: &Orientation,
     orientation: &Orientation,
     cell_matrix_height: u16,
@@ -165,9 +199,5 @@ fn calculated_y_offset(
         Orientation::BottomLeft | Orientation::Bottom | Orientation::BottomRight => height,
     };
 
-    return if anchor_offset >= orientation_offset {
-        anchor_offset - orientation_offset
-    } else {
-        0
-    };
+    return anchor_offset.saturating_sub(orientation_offset);
 }
@@ -0,0 +1,60 @@
+use termion::event::Key;
+
+use crate::{
+    cells::{cell_matrix::CellMatrix, vector::Vector},
+    core::events::Event,
+};
+
+/// The value held by an editable widget.
+#[derive(Clone, PartialEq)]
+pub enum WidgetValue {
+    Text(String),
+    Toggle(bool),
+    Number(i32),
+    /// Index of the chosen option.
+    Choice(usize),
+}
+
+/// Anything that can be focused and navigated to in a `Selector`.
+pub trait Widget {
+    fn name(&self) -> String;
+    fn width(&self) -> u16;
+    fn height(&self) -> u16;
+    fn set_position(&mut self, position: Vector<i32>);
+    /// Returns true if the cell is inside the widget, used to hit-test the mouse.
+    fn contains(&self, position: &Vector<u16>) -> bool;
+    /// Disabled widgets are shown but skipped when navigating.
+    fn is_disabled(&self) -> bool {
+        return false;
+    }
+
+    fn set_disabled(&mut self, _: bool) {}
+
+    /// Pressing the hotkey selects and activates the widget from anywhere in the selector.
+    fn hotkey(&self) -> Option<char> {
+        return None;
+    }
+
+    fn select(&mut self);
+    fn deselect(&mut self);
+    /// Handles a key while the widget is selected. Returns `None` if the key wasn't used, so the
+    /// selector can navigate with it.
+    fn handle_key(&mut self, key: Key) -> Option<Event>;
+    /// Advances the animations. Returns true if anything changed.
+    fn update(&mut self, frame_duration: f64) -> bool;
+    fn render(&mut self, cell_matrix: &mut CellMatrix);
+    /// Drops the cells erased since the last render, for renders that start from the backdrop.
+    fn discard_erased(&mut self);
+
+    fn value(&self) -> Option<WidgetValue> {
+        return None;
+    }
+
+    fn set_value(&mut self, _: WidgetValue) {}
+
+    /// Replaces the rows of widgets that show a collection, one string per column.
+    fn set_rows(&mut self, _: Vec<Vec<String>>) {}
+
+    /// Replaces the options of widgets that choose between them.
+    fn set_options(&mut self, _: Vec<String>) {}
+}